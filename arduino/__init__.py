@@ -2,44 +2,114 @@
 This module provides access to built-in Arduino functions.
 
 This file contains the signature of the built-in functions
-so that the IDEs can provide intelligent code completion, and
-DSPython compiler will not compile this file itself
-while it compiles the others.
-
-The real implementation of these function can be found
-in the rest files located in this directory.
-Especially, the low-level functions are defined in the wrapper file,
-which is located in the `include` directory.
+so that the IDEs can provide intelligent code completion.
+A function whose body is `...` declares a function which is defined
+in the Arduino core or the wrapper files located in the `include` directory.
+
+Only the names imported from this module are visible to a program.
+The Python builtins such as `print` and `abs` are declared in `builtins.py`.
 """
 
-from typing import Union
+from typing import Callable, overload
 
+from arduino.builtins import println
 from arduino.constants import *
-from arduino.uno_pins import *
+from arduino.ffi import extern
+from arduino.math import (
+    acos,
+    asin,
+    atan,
+    atan2,
+    cbrt,
+    ceil,
+    constrain,
+    cos,
+    cosh,
+    degrees,
+    exp,
+    fabs,
+    floor,
+    fmod,
+    hypot,
+    log,
+    log10,
+    map,
+    radians,
+    random,
+    random_seed,
+    sin,
+    sinh,
+    sqrt,
+    tan,
+    tanh,
+    trunc,
+)
+from arduino.types import *
+from arduino.pins import *
+
+
+def pin_mode(_pin: uint8, _mode: uint8) -> None:
+    ...
+
+
+# Measure the length of a pulse in microseconds, or return 0 after the timeout
+@overload
+def pulse_in(_pin: uint8, _state: uint8) -> uint32:
+    ...
+
+
+@overload
+def pulse_in(_pin: uint8, _state: uint8, _timeout: uint32) -> uint32:
+    ...
+
+
+# Generate a square wave of the given frequency until `no_tone` is called
+@overload
+def tone(_pin: uint8, _frequency: uint16) -> None:
+    ...
+
+
+# Generate a square wave of the given frequency for the duration in milliseconds
+@overload
+def tone(_pin: uint8, _frequency: uint16, _duration: uint32) -> None:
+    ...
+
+
+def no_tone(_pin: uint8) -> None:
+    ...
+
+
+# Shift out a byte one bit at a time in LSBFIRST or MSBFIRST order
+def shift_out(_data_pin: uint8, _clock_pin: uint8, _bit_order: uint8, _value: uint8) -> None:
+    ...
+
+
+def shift_in(_data_pin: uint8, _clock_pin: uint8, _bit_order: uint8) -> uint8:
+    ...
 
 
-# noinspection PyShadowingBuiltins
-def print(_str: object) -> None:
+def delay(_milliseconds: uint32) -> None:
     ...
 
 
-def println(_str: object) -> None:
+def delay_microseconds(_microseconds: uint16) -> None:
     ...
 
 
-def pin_mode(_pin: int, _mode: int) -> None:
+def millis() -> uint32:
     ...
 
 
-def pulse_in(_pin: int, _state: int) -> float:
+def micros() -> uint32:
     ...
 
 
-def delay(_milliseconds: int) -> None:
+# Return the number of bytes received and not read yet
+def serial_available() -> int:
     ...
 
 
-def is_serial_available() -> bool:
+def is_serial_available() -> int:
     ...
 
 
@@ -47,46 +117,70 @@ def serial_begin(_baudrate: int) -> None:
     ...
 
 
-# noinspection PyShadowingBuiltins
-def input() -> int:
+# Read a byte from the serial port, or return -1 if there is no data
+def serial_read() -> int:
     ...
 
 
-def flush() -> None:
+# Return the next byte without removing it, or -1 if there is no data
+def serial_peek() -> int:
+    ...
+
+
+# The time to wait for the data in serial_read_line and serial_parse_* (1000 ms by default)
+def serial_set_timeout(_milliseconds: uint32) -> None:
+    ...
+
+
+# Read a line without the line ending, which is overwritten by the next call.
+# The buffer includes the terminator and holds 64 bytes at most.
+def serial_read_line(_buffer_size: int) -> str:
+    ...
+
+
+# Return 0 if no number is received before the timeout
+def serial_parse_int() -> int32:
     ...
 
 
-def digital_write(_pin: int, _level: int) -> None:
+def serial_parse_float() -> float:
     ...
 
 
-def analog_write(_pin: int, _level: int) -> None:
+def flush() -> None:
     ...
 
 
-def digital_read(_pin: int) -> int:
+def attach_interrupt(_pin: uint8, _handler: Callable[[], None], _mode: uint8) -> None:
     ...
 
 
-def analog_read(_pin: int) -> int:
+def detach_interrupt(_pin: uint8) -> None:
     ...
 
 
-def radians(_deg: Union[int, float]) -> float:
+@extern("enable_interrupts")
+def interrupts() -> None:
     ...
 
 
-def degrees(_rad: Union[int, float]) -> float:
+@extern("disable_interrupts")
+def no_interrupts() -> None:
     ...
 
 
-def sin(_rad: Union[int, float]) -> float:
+def digital_write(_pin: uint8, _level: uint8) -> None:
     ...
 
 
-def cos(_rad: Union[int, float]) -> float:
+def analog_write(_pin: uint8, _level: uint8) -> None:
     ...
 
 
-def tan(_rad: Union[int, float]) -> float:
+def digital_read(_pin: uint8) -> int:
     ...
+
+
+def analog_read(_pin: uint8) -> int:
+    ...
+
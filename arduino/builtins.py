@@ -0,0 +1,54 @@
+"""
+The functions visible in every module without import.
+
+Like the other stubs, a function whose body is `...`
+is defined in the wrapper files located in the `include` directory.
+"""
+
+from typing import overload
+
+from arduino.math import abs, max, min, pow, round
+
+
+# noinspection PyShadowingBuiltins
+def print(*_values: object, sep: str = " ", end: str = "\n") -> None:
+    ...
+
+
+def println(*_values: object, sep: str = " ", end: str = "\r\n") -> None:
+    ...
+
+
+# noinspection PyShadowingBuiltins
+def input() -> int:
+    ...
+
+
+# noinspection PyShadowingBuiltins
+@overload
+def len(_str: str) -> int:
+    ...
+
+
+# noinspection PyShadowingBuiltins
+@overload
+def int(_x: float) -> int:
+    ...
+
+
+# noinspection PyShadowingBuiltins
+@overload
+def int(_x: int) -> int:
+    ...
+
+
+# noinspection PyShadowingBuiltins
+@overload
+def float(_x: float) -> float:
+    ...
+
+
+# noinspection PyShadowingBuiltins
+@overload
+def float(_x: int) -> float:
+    ...
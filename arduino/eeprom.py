@@ -0,0 +1,27 @@
+"""
+The EEPROM which keeps the values while the board is turned off.
+"""
+
+from arduino.ffi import extern
+from arduino.types import *
+
+
+@extern("eeprom_read")
+def read(_address: int) -> uint8:
+    ...
+
+
+@extern("eeprom_write")
+def write(_address: int, _value: uint8) -> None:
+    ...
+
+
+# Write only if the value differs to save the lifetime of the EEPROM
+@extern("eeprom_update")
+def update(_address: int, _value: uint8) -> None:
+    ...
+
+
+@extern("eeprom_length")
+def length() -> int:
+    ...
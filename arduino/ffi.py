@@ -0,0 +1,44 @@
+"""
+Declarations of C functions and interrupt handlers.
+
+This module is only for linters, so DSPython compiler does not compile it.
+"""
+
+from typing import Callable, TypeVar
+
+_F = TypeVar("_F", bound=Callable)
+
+
+def extern(_symbol: str) -> Callable[[_F], _F]:
+    """
+    Declare a C function of the given symbol with the annotated signature.
+
+    e.g.
+    @extern("millis")
+    def millis() -> uint32: ...
+    """
+    return lambda f: f
+
+
+def interrupt(f: Callable[[], None]) -> Callable[[], None]:
+    """
+    Declare a handler which can be passed to `attach_interrupt`.
+    The globals accessed by the handler are volatile.
+
+    e.g.
+    @interrupt
+    def on_press() -> None: ...
+    """
+    return f
+
+
+def isr(_vector: str) -> Callable[[Callable[[], None]], Callable[[], None]]:
+    """
+    Define the handler of an interrupt vector named as in avr-libc.
+    The globals accessed by the handler are volatile.
+
+    e.g.
+    @isr("TIMER1_COMPA_vect")
+    def on_tick() -> None: ...
+    """
+    return lambda f: f
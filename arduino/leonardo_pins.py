@@ -0,0 +1,46 @@
+def analog_input_to_digital_pin(p: int) -> int:
+    if p < 12:
+        return p + 18
+    else:
+        return -1
+
+
+def digital_pin_to_interrupt(p: int) -> int:
+    if p == 3:
+        return 0
+    elif p == 2:
+        return 1
+    elif p == 0:
+        return 2
+    elif p == 1:
+        return 3
+    elif p == 7:
+        return 4
+
+    # Not an interrupt
+    return -1
+
+
+SS = 17
+MOSI = 16
+MISO = 14
+SCK = 15
+
+SDA = 2
+SCL = 3
+
+LED_BUILTIN = 13
+
+A0 = 18
+A1 = 19
+A2 = 20
+A3 = 21
+A4 = 22
+A5 = 23
+# The analog inputs shared with the digital pins 4, 6, 8, 9, 10 and 12
+A6 = 24
+A7 = 25
+A8 = 26
+A9 = 27
+A10 = 28
+A11 = 29
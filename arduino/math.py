@@ -1,59 +1,355 @@
+from typing import overload
+
 from arduino.constants import DEG_TO_RAD, RAD_TO_DEG
+from arduino.ffi import extern
+from arduino.types import *
+
+
+@overload
+def abs(x: int) -> int:
+    if x > 0:
+        return x
+    else:
+        return 0-x
 
 
-def abs__i__(x: int) -> int:
+@overload
+def abs(x: int32) -> int32:
     if x > 0:
         return x
     else:
         return 0-x
 
 
-def abs__f__(x: float) -> float:
+@overload
+def abs(x: uint32) -> uint32:
+    return x
+
+
+@overload
+def abs(x: float) -> float:
     if x > 0.0:
         return x
     else:
         return 0.0-x
 
 
-def min__i__(a: int, b: int) -> int:
+@overload
+def min(a: int, b: int) -> int:
+    if a < b:
+        return a
+    else:
+        return b
+
+
+@overload
+def min(a: int32, b: int32) -> int32:
+    if a < b:
+        return a
+    else:
+        return b
+
+
+@overload
+def min(a: uint32, b: uint32) -> uint32:
     if a < b:
         return a
     else:
         return b
 
 
-def min__f__(a: float, b: float) -> float:
+@overload
+def min(a: float, b: float) -> float:
     if a < b:
         return a
     else:
         return b
 
 
-def max__i__(a: int, b: int) -> int:
+@overload
+def max(a: int, b: int) -> int:
+    if a > b:
+        return a
+    else:
+        return b
+
+
+@overload
+def max(a: int32, b: int32) -> int32:
     if a > b:
         return a
     else:
         return b
 
 
-def max__f__(a: float, b: float) -> float:
+@overload
+def max(a: uint32, b: uint32) -> uint32:
     if a > b:
         return a
     else:
         return b
 
 
-def radians__i__(deg: int) -> float:
+@overload
+def max(a: float, b: float) -> float:
+    if a > b:
+        return a
+    else:
+        return b
+
+
+@overload
+def radians(deg: int) -> float:
     return deg * DEG_TO_RAD
 
 
-def radians__f__(deg: float) -> float:
+@overload
+def radians(deg: float) -> float:
     return deg * DEG_TO_RAD
 
 
-def degrees__i__(rad: int) -> float:
+@overload
+def degrees(rad: int) -> float:
     return rad * RAD_TO_DEG
 
 
-def degrees__f__(rad: float) -> float:
+@overload
+def degrees(rad: float) -> float:
     return rad * RAD_TO_DEG
+
+
+@overload
+def constrain(x: int, low: int, high: int) -> int:
+    if x < low:
+        return low
+    elif x > high:
+        return high
+    else:
+        return x
+
+
+@overload
+def constrain(x: int32, low: int32, high: int32) -> int32:
+    if x < low:
+        return low
+    elif x > high:
+        return high
+    else:
+        return x
+
+
+@overload
+def constrain(x: float, low: float, high: float) -> float:
+    if x < low:
+        return low
+    elif x > high:
+        return high
+    else:
+        return x
+
+
+# Re-map a number from one range to another.
+# The integers are computed in 32 bits to avoid overflow like Arduino.
+# noinspection PyShadowingBuiltins
+@overload
+def map(x: int32, in_min: int32, in_max: int32, out_min: int32, out_max: int32) -> int32:
+    return (x - in_min) * (out_max - out_min) // (in_max - in_min) + out_min
+
+
+# noinspection PyShadowingBuiltins
+@overload
+def map(x: float, in_min: float, in_max: float, out_min: float, out_max: float) -> float:
+    return (x - in_min) * (out_max - out_min) / (in_max - in_min) + out_min
+
+
+# A pseudo-random number from 0 to `_max` (exclusive)
+@overload
+def random(_max: int32) -> int32:
+    ...
+
+
+# A pseudo-random number from `_min` to `_max` (exclusive)
+@overload
+def random(_min: int32, _max: int32) -> int32:
+    ...
+
+
+@overload
+def random(_max: float) -> float:
+    ...
+
+
+@overload
+def random(_min: float, _max: float) -> float:
+    ...
+
+
+# Initialize the pseudo-random number generator, e.g. with `analog_read` of an unconnected pin
+def random_seed(_seed: uint32) -> None:
+    ...
+
+
+# The functions of avr-libc, where double is as wide as float.
+# Integers are converted to float when they are passed.
+def sin(_rad: float) -> float:
+    ...
+
+
+def cos(_rad: float) -> float:
+    ...
+
+
+def tan(_rad: float) -> float:
+    ...
+
+
+def asin(_x: float) -> float:
+    ...
+
+
+def acos(_x: float) -> float:
+    ...
+
+
+def atan(_x: float) -> float:
+    ...
+
+
+def atan2(_y: float, _x: float) -> float:
+    ...
+
+
+def sinh(_x: float) -> float:
+    ...
+
+
+def cosh(_x: float) -> float:
+    ...
+
+
+def tanh(_x: float) -> float:
+    ...
+
+
+def sqrt(_x: float) -> float:
+    ...
+
+
+def cbrt(_x: float) -> float:
+    ...
+
+
+# The length of the hypotenuse, sqrt(x * x + y * y) without overflow
+def hypot(_x: float, _y: float) -> float:
+    ...
+
+
+def exp(_x: float) -> float:
+    ...
+
+
+# The natural logarithm
+def log(_x: float) -> float:
+    ...
+
+
+def log10(_x: float) -> float:
+    ...
+
+
+def fabs(_x: float) -> float:
+    ...
+
+
+# The remainder with the sign of `_x` unlike `%`, e.g. fmod(-1.0, 3.0) == -1.0
+def fmod(_x: float, _y: float) -> float:
+    ...
+
+
+@extern("pow")
+def _pow(_base: float, _exponent: float) -> float:
+    ...
+
+
+@extern("floor")
+def _floor(_x: float) -> float:
+    ...
+
+
+@extern("ceil")
+def _ceil(_x: float) -> float:
+    ...
+
+
+@extern("trunc")
+def _trunc(_x: float) -> float:
+    ...
+
+
+# Round to the nearest integer, and the halfway cases to the even one
+@extern("lrint")
+def _lrint(_x: float) -> int32:
+    ...
+
+
+# The exponent of an integer must not be negative
+# noinspection PyShadowingBuiltins
+@overload
+def pow(base: int32, exponent: int) -> int32:
+    result: int32 = 1
+    while exponent > 0:
+        if exponent % 2 == 1:
+            result = result * base
+        base = base * base
+        exponent = exponent // 2
+    return result
+
+
+# noinspection PyShadowingBuiltins
+@overload
+def pow(base: float, exponent: float) -> float:
+    return _pow(base, exponent)
+
+
+@overload
+def floor(x: int) -> int:
+    return x
+
+
+@overload
+def floor(x: float) -> int32:
+    return _lrint(_floor(x))
+
+
+@overload
+def ceil(x: int) -> int:
+    return x
+
+
+@overload
+def ceil(x: float) -> int32:
+    return _lrint(_ceil(x))
+
+
+# Round toward zero, e.g. trunc(-2.5) == -2
+@overload
+def trunc(x: int) -> int:
+    return x
+
+
+@overload
+def trunc(x: float) -> int32:
+    return _lrint(_trunc(x))
+
+
+# Banker's rounding like Python, e.g. round(2.5) == 2
+# noinspection PyShadowingBuiltins
+@overload
+def round(x: int) -> int:
+    return x
+
+
+# noinspection PyShadowingBuiltins
+@overload
+def round(x: float) -> int32:
+    return _lrint(x)
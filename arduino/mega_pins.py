@@ -0,0 +1,51 @@
+def analog_input_to_digital_pin(p: int) -> int:
+    if p < 16:
+        return p + 54
+    else:
+        return -1
+
+
+def digital_pin_to_interrupt(p: int) -> int:
+    if p == 2:
+        return 0
+    elif p == 3:
+        return 1
+    elif p == 18:
+        return 5
+    elif p == 19:
+        return 4
+    elif p == 20:
+        return 3
+    elif p == 21:
+        return 2
+
+    # Not an interrupt
+    return -1
+
+
+SS = 53
+MOSI = 51
+MISO = 50
+SCK = 52
+
+SDA = 20
+SCL = 21
+
+LED_BUILTIN = 13
+
+A0 = 54
+A1 = 55
+A2 = 56
+A3 = 57
+A4 = 58
+A5 = 59
+A6 = 60
+A7 = 61
+A8 = 62
+A9 = 63
+A10 = 64
+A11 = 65
+A12 = 66
+A13 = 67
+A14 = 68
+A15 = 69
@@ -0,0 +1,8 @@
+"""
+The pins of the target board.
+
+This file is only for linters. The compiler imports the module of the board
+given by `--board` instead, e.g. `arduino.mega_pins` for the Mega 2560.
+"""
+
+from arduino.uno_pins import *
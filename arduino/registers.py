@@ -0,0 +1,104 @@
+"""
+The I/O registers of the target MCU selected by `--cpu`.
+
+e.g.
+from arduino.registers import DDRB, PORTB
+
+def setup():
+    global DDRB
+    DDRB = DDRB | 0x20
+
+Reading or assigning a register accesses the memory-mapped register directly.
+The compiler defines the registers of the MCU, so it does not compile this module.
+This file lists the registers of the ATmega328P for linters.
+"""
+
+from arduino.types import *
+
+PINB: uint8  # 0x23
+DDRB: uint8  # 0x24
+PORTB: uint8  # 0x25
+PINC: uint8  # 0x26
+DDRC: uint8  # 0x27
+PORTC: uint8  # 0x28
+PIND: uint8  # 0x29
+DDRD: uint8  # 0x2A
+PORTD: uint8  # 0x2B
+TIFR0: uint8  # 0x35
+TIFR1: uint8  # 0x36
+TIFR2: uint8  # 0x37
+PCIFR: uint8  # 0x3B
+EIFR: uint8  # 0x3C
+EIMSK: uint8  # 0x3D
+GPIOR0: uint8  # 0x3E
+EECR: uint8  # 0x3F
+EEDR: uint8  # 0x40
+EEARL: uint8  # 0x41
+EEARH: uint8  # 0x42
+GTCCR: uint8  # 0x43
+TCCR0A: uint8  # 0x44
+TCCR0B: uint8  # 0x45
+TCNT0: uint8  # 0x46
+OCR0A: uint8  # 0x47
+OCR0B: uint8  # 0x48
+GPIOR1: uint8  # 0x4A
+GPIOR2: uint8  # 0x4B
+SPCR: uint8  # 0x4C
+SPSR: uint8  # 0x4D
+SPDR: uint8  # 0x4E
+ACSR: uint8  # 0x50
+SMCR: uint8  # 0x53
+MCUSR: uint8  # 0x54
+MCUCR: uint8  # 0x55
+SPMCSR: uint8  # 0x57
+SPL: uint8  # 0x5D
+SPH: uint8  # 0x5E
+SREG: uint8  # 0x5F
+WDTCSR: uint8  # 0x60
+CLKPR: uint8  # 0x61
+PRR: uint8  # 0x64
+OSCCAL: uint8  # 0x66
+PCICR: uint8  # 0x68
+EICRA: uint8  # 0x69
+PCMSK0: uint8  # 0x6B
+PCMSK1: uint8  # 0x6C
+PCMSK2: uint8  # 0x6D
+TIMSK0: uint8  # 0x6E
+TIMSK1: uint8  # 0x6F
+TIMSK2: uint8  # 0x70
+ADCL: uint8  # 0x78
+ADCH: uint8  # 0x79
+ADCSRA: uint8  # 0x7A
+ADCSRB: uint8  # 0x7B
+ADMUX: uint8  # 0x7C
+DIDR0: uint8  # 0x7E
+DIDR1: uint8  # 0x7F
+TCCR1A: uint8  # 0x80
+TCCR1B: uint8  # 0x81
+TCCR1C: uint8  # 0x82
+TCNT1L: uint8  # 0x84
+TCNT1H: uint8  # 0x85
+ICR1L: uint8  # 0x86
+ICR1H: uint8  # 0x87
+OCR1AL: uint8  # 0x88
+OCR1AH: uint8  # 0x89
+OCR1BL: uint8  # 0x8A
+OCR1BH: uint8  # 0x8B
+TCCR2A: uint8  # 0xB0
+TCCR2B: uint8  # 0xB1
+TCNT2: uint8  # 0xB2
+OCR2A: uint8  # 0xB3
+OCR2B: uint8  # 0xB4
+ASSR: uint8  # 0xB6
+TWBR: uint8  # 0xB8
+TWSR: uint8  # 0xB9
+TWAR: uint8  # 0xBA
+TWDR: uint8  # 0xBB
+TWCR: uint8  # 0xBC
+TWAMR: uint8  # 0xBD
+UCSR0A: uint8  # 0xC0
+UCSR0B: uint8  # 0xC1
+UCSR0C: uint8  # 0xC2
+UBRR0L: uint8  # 0xC4
+UBRR0H: uint8  # 0xC5
+UDR0: uint8  # 0xC6
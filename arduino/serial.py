@@ -0,0 +1,90 @@
+"""
+Serial ports as objects, e.g. `Serial1.begin(9600)` and `Serial1.println("Hello")`.
+
+`Serial` is the port used by `print` and `println`. `Serial1` to `Serial3` are
+the other hardware ports, which are only available on boards such as the Mega 2560.
+They are linked with a program only when it begins them by their names, e.g. `Serial1.begin(9600)`,
+so the other methods do nothing until then, as they do for a port which does not exist on the board.
+Software ports are created by `SoftwareSerial` in `arduino.software_serial`.
+"""
+
+from arduino.ffi import extern
+from arduino.types import *
+
+
+class SerialPort:
+    """
+    A serial port referred by its handle. The methods are defined in `Serial.cc`.
+    """
+
+    @extern("serial_port_begin")
+    def begin(self, _baudrate: uint32) -> None:
+        ...
+
+    @extern("serial_port_available")
+    def available(self) -> int:
+        ...
+
+    # Return the next byte, or -1 if no data is available
+    @extern("serial_port_read")
+    def read(self) -> int:
+        ...
+
+    @extern("serial_port_peek")
+    def peek(self) -> int:
+        ...
+
+    @extern("serial_port_write")
+    def write(self, _b: uint8) -> None:
+        ...
+
+    @extern("serial_port_flush")
+    def flush(self) -> None:
+        ...
+
+    @extern("serial_port_set_timeout")
+    def set_timeout(self, _timeout: uint32) -> None:
+        ...
+
+    # The line is overwritten by the next call to read_line of any port
+    @extern("serial_port_read_line")
+    def read_line(self, _buffer_size: int) -> str:
+        ...
+
+    @extern("serial_port_parse_int")
+    def parse_int(self) -> int32:
+        ...
+
+    @extern("serial_port_parse_float")
+    def parse_float(self) -> float:
+        ...
+
+    def print(self, *_values: object, sep: str = " ", end: str = "\n") -> None:
+        ...
+
+    def println(self, *_values: object, sep: str = " ", end: str = "\r\n") -> None:
+        ...
+
+
+class _HardwareSerial1(SerialPort):
+    @extern("serial1_begin")
+    def begin(self, _baudrate: uint32) -> None:
+        ...
+
+
+class _HardwareSerial2(SerialPort):
+    @extern("serial2_begin")
+    def begin(self, _baudrate: uint32) -> None:
+        ...
+
+
+class _HardwareSerial3(SerialPort):
+    @extern("serial3_begin")
+    def begin(self, _baudrate: uint32) -> None:
+        ...
+
+
+Serial: SerialPort = 0
+Serial1: _HardwareSerial1 = 1
+Serial2: _HardwareSerial2 = 2
+Serial3: _HardwareSerial3 = 3
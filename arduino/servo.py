@@ -0,0 +1,39 @@
+"""
+Servo motors with the Servo library.
+
+Servos are referred by their index from 0, e.g. `servo.attach(0, 9)`.
+Up to 12 servos can be used on most boards, and the calls with a larger index do nothing.
+"""
+
+from arduino.ffi import extern
+from arduino.types import *
+
+
+@extern("servo_attach")
+def attach(_servo: uint8, _pin: uint8) -> None:
+    ...
+
+
+@extern("servo_detach")
+def detach(_servo: uint8) -> None:
+    ...
+
+
+@extern("servo_write")
+def write(_servo: uint8, _angle: int) -> None:
+    ...
+
+
+@extern("servo_write_microseconds")
+def write_microseconds(_servo: uint8, _us: int) -> None:
+    ...
+
+
+@extern("servo_read")
+def read(_servo: uint8) -> int:
+    ...
+
+
+@extern("servo_attached")
+def attached(_servo: uint8) -> uint8:
+    ...
@@ -0,0 +1,16 @@
+"""
+Serial ports on any digital pins with the SoftwareSerial library.
+
+`SoftwareSerial(10, 11)` creates a port receiving on the pin 10 and
+transmitting on the pin 11. Up to 4 software ports can be created,
+and only one of them can receive at a time.
+"""
+
+from arduino.ffi import extern
+from arduino.serial import SerialPort
+from arduino.types import *
+
+
+@extern("software_serial_new")
+def SoftwareSerial(_rx: uint8, _tx: uint8) -> SerialPort:
+    ...
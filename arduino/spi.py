@@ -0,0 +1,36 @@
+"""
+SPI communication with the SPI library.
+"""
+
+from arduino.ffi import extern
+from arduino.types import *
+
+SPI_MODE0 = 0x00
+SPI_MODE1 = 0x04
+SPI_MODE2 = 0x08
+SPI_MODE3 = 0x0C
+
+
+@extern("spi_begin")
+def begin() -> None:
+    ...
+
+
+@extern("spi_end")
+def end() -> None:
+    ...
+
+
+@extern("spi_begin_transaction")
+def begin_transaction(_clock: uint32, _bit_order: uint8, _data_mode: uint8) -> None:
+    ...
+
+
+@extern("spi_end_transaction")
+def end_transaction() -> None:
+    ...
+
+
+@extern("spi_transfer")
+def transfer(_data: uint8) -> uint8:
+    ...
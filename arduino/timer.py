@@ -0,0 +1,32 @@
+"""
+Timer1 which calls the handler of `TIMER1_COMPA_vect` periodically.
+
+e.g.
+@isr("TIMER1_COMPA_vect")
+def sample():
+    ...
+
+Timer1 is also used by the servo library, so they cannot be used together.
+"""
+
+from arduino.ffi import extern
+from arduino.types import *
+
+
+# Count to `compare` in CTC mode with the clock divided by `prescaler`,
+# which is one of 1, 8, 64, 256 and 1024.
+# The interrupt occurs at F_CPU / prescaler / (compare + 1) Hz.
+@extern("timer1_start")
+def start(_prescaler: uint16, _compare: uint16) -> None:
+    ...
+
+
+# Choose the prescaler and the compare value for the given frequency
+@extern("timer1_set_frequency")
+def set_frequency(_frequency: uint32) -> None:
+    ...
+
+
+@extern("timer1_stop")
+def stop() -> None:
+    ...
@@ -0,0 +1,19 @@
+"""
+The C types which can be used in type annotations.
+
+This module is only for linters, so DSPython compiler does not compile it.
+The compiler maps these names to the types of LLVM directly.
+"""
+
+from typing import Union
+
+int8 = int
+int32 = int
+uint8 = int
+uint16 = int
+uint32 = int
+
+# A module-level variable such as `threshold: Persistent[int] = 512` is kept in the EEPROM.
+# Its initial value is written when the .eep file is uploaded, and it keeps the
+# last assigned value after reset.
+Persistent = Union
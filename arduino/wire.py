@@ -0,0 +1,51 @@
+"""
+I2C communication with the Wire library.
+"""
+
+from arduino.ffi import extern
+from arduino.types import *
+
+
+@extern("wire_begin")
+def begin() -> None:
+    ...
+
+
+@extern("wire_end")
+def end() -> None:
+    ...
+
+
+@extern("wire_set_clock")
+def set_clock(_frequency: uint32) -> None:
+    ...
+
+
+@extern("wire_begin_transmission")
+def begin_transmission(_address: uint8) -> None:
+    ...
+
+
+@extern("wire_end_transmission")
+def end_transmission() -> uint8:
+    ...
+
+
+@extern("wire_request_from")
+def request_from(_address: uint8, _quantity: uint8) -> uint8:
+    ...
+
+
+@extern("wire_write")
+def write(_data: uint8) -> int:
+    ...
+
+
+@extern("wire_available")
+def available() -> int:
+    ...
+
+
+@extern("wire_read")
+def read() -> int:
+    ...
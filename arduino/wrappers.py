@@ -0,0 +1,78 @@
+"""
+The functions which the code generator calls directly.
+
+Like the other stubs, these functions are defined in the wrapper files
+located in the `include` directory.
+"""
+
+from arduino.types import *
+
+
+def print__i__(_n: int) -> None:
+    ...
+
+
+def print__f__(_n: float) -> None:
+    ...
+
+
+def print__s__(_s: str) -> None:
+    ...
+
+
+# The string is in the program memory
+def print_flash(_s: str) -> None:
+    ...
+
+
+def print__b__(_b: uint8) -> None:
+    ...
+
+
+def print__l__(_n: int32) -> None:
+    ...
+
+
+def print__u__(_n: uint16) -> None:
+    ...
+
+
+def print__ul__(_n: uint32) -> None:
+    ...
+
+
+def print_base__l__(_n: int32, _base: uint8, _upper: uint8) -> None:
+    ...
+
+
+def print_base__ul__(_n: uint32, _base: uint8, _upper: uint8) -> None:
+    ...
+
+
+def print_digits__f__(_n: float, _digits: uint8) -> None:
+    ...
+
+
+def str_concat(_dst: str, _a: str, _b: str, _size: int) -> None:
+    ...
+
+
+def str_compare(_a: str, _b: str) -> int:
+    ...
+
+
+def str_at(_s: str, _i: int) -> int:
+    ...
+
+
+def print_select(_port: uint8) -> None:
+    ...
+
+
+# Copy a variable annotated with Persistent between the EEPROM and the value pointed
+def persistent_read(_value: str, _address: int, _size: uint8) -> None:
+    ...
+
+
+def persistent_write(_value: str, _address: int, _size: uint8) -> None:
+    ...
@@ -0,0 +1,36 @@
+use std::env;
+use std::fs::{read_dir, write};
+use std::path::{Path, PathBuf};
+
+/// Generate the list of the wrappers embedded in the compiler from the contents of `include`,
+/// so that a new wrapper is never left out
+fn main() {
+    let include_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("include");
+    println!("cargo:rerun-if-changed={}", include_dir.display());
+
+    let mut paths = read_dir(&include_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let extension = path.extension().and_then(|e| e.to_str());
+            extension == Some("cc") || extension == Some("hh")
+        })
+        .collect::<Vec<PathBuf>>();
+    paths.sort();
+    let entries = paths
+        .iter()
+        .map(|path| {
+            format!(
+                "    ({:?}, include_str!({:?})),\n",
+                path.file_name().unwrap().to_str().unwrap(),
+                path.canonicalize().unwrap()
+            )
+        })
+        .collect::<String>();
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    write(
+        out_dir.join("wrapper_files.rs"),
+        format!("&[\n{}]\n", entries),
+    )
+    .unwrap();
+}
@@ -0,0 +1,38 @@
+use std::env;
+use std::fs::{read_dir, write};
+use std::path::{Path, PathBuf};
+
+/// Generate the lists of the standard libraries and the wrapper headers embedded in the compiler
+/// from the contents of `arduino` and `include`, so that a new file is never left out
+fn main() {
+    let root_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    for (dir, extension, list) in [
+        ("arduino", "py", "stdlib_files.rs"),
+        ("include", "hh", "wrapper_headers.rs"),
+    ]
+    .iter()
+    {
+        let dir_path = root_dir.join(dir);
+        println!("cargo:rerun-if-changed={}", dir_path.display());
+
+        let mut paths = read_dir(&dir_path)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().and_then(|e| e.to_str()) == Some(*extension))
+            .collect::<Vec<PathBuf>>();
+        paths.sort();
+        let entries = paths
+            .iter()
+            .map(|path| {
+                let file_name = path.file_name().unwrap().to_str().unwrap();
+                format!(
+                    "    ({:?}, include_str!({:?})),\n",
+                    format!("{}/{}", dir, file_name),
+                    path.canonicalize().unwrap()
+                )
+            })
+            .collect::<String>();
+        write(out_dir.join(list), format!("&[\n{}]\n", entries)).unwrap();
+    }
+}
@@ -38,6 +38,7 @@ impl fmt::Display for LLVMCompileError {
                 format!("Expected '{}', but found '{}'", expected, but)
             }
             LLVMCompileErrorType::NotImplemented(desc) => format!("{}", desc),
+            LLVMCompileErrorType::ImportError(desc) => format!("{}", desc),
         };
 
         let loc_string = if let Some(loc) = self.location {
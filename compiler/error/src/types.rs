@@ -12,6 +12,8 @@ pub enum LLVMCompileErrorType {
     TypeError(String, String),
 
     NotImplemented(String),
+
+    ImportError(String),
 }
 
 impl fmt::Display for LLVMCompileErrorType {
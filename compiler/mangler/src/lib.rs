@@ -1,16 +1,160 @@
-use dsp_compiler_value::value::ValueType;
+use dsp_compiler_value::value::{ValueType, ValueTypeGroup};
 
-pub fn get_mangled_name(origin: &String, at: ValueType) -> String {
-    let origin = &mut origin.clone();
+/// Mangle the name of a function with the types of its arguments.
+/// e.g. `min(int, float)` is mangled as `min__i_f__`.
+pub fn get_mangled_name(origin: &str, arg_types: &[ValueType]) -> String {
+    let type_codes = arg_types
+        .iter()
+        .map(|arg_type| get_type_code(*arg_type))
+        .collect::<Option<Vec<&str>>>();
 
-    origin.push_str(match at {
-        ValueType::Str => "__s__",
-        ValueType::I8 | ValueType::I16 | ValueType::Bool => "__i__",
-        ValueType::F32 => "__f__",
-        _ => "",
-    });
+    match type_codes {
+        Some(type_codes) if !type_codes.is_empty() => {
+            format!("{}__{}__", origin, type_codes.join("_"))
+        }
+        _ => origin.to_string(),
+    }
+}
+
+/// Split a mangled name into the original name and the types of its arguments
+pub fn demangle(name: &str) -> Option<(&str, Vec<ValueType>)> {
+    let name = name.strip_suffix("__")?;
+    let separator = name.rfind("__")?;
+    let (origin, type_codes) = (&name[..separator], &name[separator + 2..]);
+    if origin.is_empty() || type_codes.is_empty() {
+        return None;
+    }
+
+    let arg_types = type_codes
+        .split('_')
+        .map(get_type_from_code)
+        .collect::<Option<Vec<ValueType>>>()?;
+
+    Some((origin, arg_types))
+}
+
+fn get_type_code(at: ValueType) -> Option<&'static str> {
+    match at {
+        ValueType::Str => Some("s"),
+        ValueType::Bool => Some("b"),
+        ValueType::I8 | ValueType::I16 => Some("i"),
+        ValueType::I32 => Some("l"),
+        ValueType::U8 | ValueType::U16 => Some("u"),
+        ValueType::U32 => Some("ul"),
+        ValueType::F32 => Some("f"),
+        ValueType::F64 => Some("d"),
+        _ => None,
+    }
+}
+
+fn get_type_from_code(code: &str) -> Option<ValueType> {
+    match code {
+        "s" => Some(ValueType::Str),
+        "b" => Some(ValueType::Bool),
+        "i" => Some(ValueType::I16),
+        "l" => Some(ValueType::I32),
+        "u" => Some(ValueType::U16),
+        "ul" => Some(ValueType::U32),
+        "f" => Some(ValueType::F32),
+        "d" => Some(ValueType::F64),
+        _ => None,
+    }
+}
+
+/// The cost to implicitly convert an argument to the type of a parameter.
+/// Integers prefer the narrowest parameter and then the same signedness.
+/// Narrowing an integer or changing its signedness is only allowed for a constant
+/// such as a literal which the parameter can hold, e.g. `min(millis(), 100)`.
+/// Returns `None` if the conversion is not allowed.
+pub fn get_conversion_cost(from: ValueType, constant: Option<i64>, to: ValueType) -> Option<usize> {
+    if get_type_code(from) == get_type_code(to) {
+        return Some(0);
+    }
+
+    let widening = from.get_bitwidth() < to.get_bitwidth();
+    let rank = if to.get_bitwidth() > 16 { 4 } else { 2 };
+    match (from.get_group(), to.get_group()) {
+        (ValueTypeGroup::Bool, ValueTypeGroup::UInt) => Some(rank),
+        (ValueTypeGroup::Bool, ValueTypeGroup::Int) => Some(rank + 1),
+        // Widening
+        (ValueTypeGroup::Int, ValueTypeGroup::Int)
+        | (ValueTypeGroup::UInt, ValueTypeGroup::UInt)
+            if widening =>
+        {
+            Some(rank)
+        }
+        (ValueTypeGroup::UInt, ValueTypeGroup::Int) if widening => Some(rank + 1),
+        (ValueTypeGroup::Int, _) | (ValueTypeGroup::UInt, _) if matches!(constant, Some(constant) if can_hold(to, constant)) => {
+            Some(rank)
+        }
+        (ValueTypeGroup::Float, ValueTypeGroup::Float) if widening => Some(1),
+        (ValueTypeGroup::Bool, ValueTypeGroup::Float)
+        | (ValueTypeGroup::Int, ValueTypeGroup::Float)
+        | (ValueTypeGroup::UInt, ValueTypeGroup::Float) => Some(7),
+        _ => None,
+    }
+}
+
+/// Whether an integer type can hold a constant
+fn can_hold(value_type: ValueType, constant: i64) -> bool {
+    let bits = value_type.get_bitwidth() as u32;
+    let constant = constant as i128;
+    match value_type.get_group() {
+        ValueTypeGroup::Int => -(1 << (bits - 1)) <= constant && constant < 1 << (bits - 1),
+        ValueTypeGroup::UInt => 0 <= constant && constant < 1 << bits,
+        _ => false,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum OverloadError {
+    NoMatch,
+    Ambiguous(Vec<String>),
+}
+
+/// Choose the overload which needs the cheapest conversions of the arguments.
+/// `constants` are the values of the arguments which are integer constants.
+pub fn resolve_overload<'n>(
+    overloads: &'n [(String, Vec<ValueType>)],
+    arg_types: &[ValueType],
+    constants: &[Option<i64>],
+) -> Result<&'n str, OverloadError> {
+    let candidates = overloads
+        .iter()
+        .filter(|(_, param_types)| param_types.len() == arg_types.len())
+        .collect::<Vec<&(String, Vec<ValueType>)>>();
+    // The only overload taking as many arguments is called like a function which
+    // is not overloaded, so `pulse_in(pin, HIGH)` converts its arguments to uint8
+    if let [(name, _)] = candidates.as_slice() {
+        return Ok(name.as_str());
+    }
+
+    let mut best: Vec<&'n str> = vec![];
+    let mut best_cost = usize::MAX;
+
+    for (name, param_types) in candidates.into_iter() {
+        let cost = arg_types
+            .iter()
+            .zip(constants.iter())
+            .zip(param_types.iter())
+            .map(|((from, constant), to)| get_conversion_cost(*from, *constant, *to))
+            .sum::<Option<usize>>();
+
+        match cost {
+            Some(cost) if cost < best_cost => {
+                best_cost = cost;
+                best = vec![name.as_str()];
+            }
+            Some(cost) if cost == best_cost => best.push(name.as_str()),
+            _ => {}
+        }
+    }
 
-    {
-        origin.clone()
+    match best.len() {
+        0 => Err(OverloadError::NoMatch),
+        1 => Ok(best[0]),
+        _ => Err(OverloadError::Ambiguous(
+            best.iter().map(|name| name.to_string()).collect(),
+        )),
     }
 }
@@ -1,10 +1,34 @@
 #[derive(Clone)]
 pub struct CompilerFlags {
     pub optimization_level: u8,
+    // Directories to search for user modules
+    pub module_paths: Vec<String>,
+    // Directory which contains the standard libraries to use instead of the embedded ones
+    pub stdlib_path: Option<String>,
+    // The target microcontroller such as atmega328p
+    pub cpu: String,
+    // Keep the string literals printed by print in the program memory instead of SRAM
+    pub flash_strings: bool,
+    // The module which defines the pins of the target board, imported as `arduino.pins`
+    pub pin_module: String,
 }
 
 impl CompilerFlags {
-    pub fn new(optimization_level: u8) -> Self {
-        CompilerFlags { optimization_level }
+    pub fn new(
+        optimization_level: u8,
+        module_paths: Vec<String>,
+        stdlib_path: Option<String>,
+        cpu: String,
+        flash_strings: bool,
+        pin_module: String,
+    ) -> Self {
+        CompilerFlags {
+            optimization_level,
+            module_paths,
+            stdlib_path,
+            cpu,
+            flash_strings,
+            pin_module,
+        }
     }
 }
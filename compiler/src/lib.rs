@@ -1,5 +1,7 @@
-use std::fs::{read_dir, read_to_string};
+use std::collections::HashSet;
+use std::fs::read_to_string;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use inkwell::builder::Builder;
 use inkwell::context::Context;
@@ -10,15 +12,15 @@ use inkwell::targets::{TargetData, TargetTriple};
 use inkwell::OptimizationLevel;
 
 use dsp_compiler_error::{LLVMCompileError, LLVMCompileErrorType};
-use dsp_python_codegen::{get_doc, CodeGen};
+use dsp_python_codegen::{get_doc, CodeGen, BUILTINS_MODULE, MAIN_MODULE};
 use dsp_python_parser::parser::parse_program;
 use dsp_python_parser::{ast, CompileError};
 
 pub use crate::flags::*;
-use crate::llvm_prototypes::generate_prototypes;
+use crate::stdlib::{read_stdlib_file, verify_prototypes};
 
 pub mod flags;
-mod llvm_prototypes;
+mod stdlib;
 
 type CompileResult<T> = Result<T, LLVMCompileError>;
 
@@ -28,7 +30,12 @@ pub struct Compiler<'a, 'ctx> {
 
     codegen: CodeGen<'a, 'ctx>,
     pass_manager: PassManager<Module<'ctx>>,
-    program: ast::Program,
+    program: Option<ast::Program>,
+
+    // Modules which are already compiled
+    imported_modules: HashSet<String>,
+    // Modules being compiled to detect circular imports
+    import_stack: Vec<String>,
 }
 
 impl<'a, 'ctx> Compiler<'a, 'ctx> {
@@ -41,88 +48,226 @@ impl<'a, 'ctx> Compiler<'a, 'ctx> {
         pass_manager: PassManager<Module<'ctx>>,
         program: ast::Program,
     ) -> Self {
+        let codegen = CodeGen::new(
+            context,
+            builder,
+            module,
+            &compiler_flags.cpu,
+            compiler_flags.flash_strings,
+        );
         Compiler {
             source_path,
             compiler_flags,
-            codegen: CodeGen::new(context, builder, module),
+            codegen,
             pass_manager,
-            program,
+            program: Some(program),
+            imported_modules: HashSet::new(),
+            import_stack: vec![MAIN_MODULE.to_string()],
         }
     }
 
     /// Compile main program
     pub fn compile(&mut self) -> CompileResult<()> {
-        let (statements, _doc_string) = get_doc(&self.program.statements);
+        let program = self
+            .program
+            .take()
+            .expect("The program is already compiled");
+        let (statements, _doc_string) = get_doc(&program.statements);
 
-        for statement in statements.iter() {
-            if let ast::StatementType::Expression { ref expression } = statement.node {
-                self.codegen.emit_expr(&expression)?;
-            } else {
-                self.codegen.emit_stmt(&statement)?;
-            }
-        }
-        Ok(())
+        self.compile_statements(statements)
     }
 
     /// Compile the given module
     pub fn compile_module(&mut self, module: ast::Program) -> CompileResult<()> {
         let (statements, _doc_string) = get_doc(&module.statements);
 
+        self.compile_statements(statements)
+    }
+
+    fn compile_statements(&mut self, statements: &[ast::Statement]) -> CompileResult<()> {
         for statement in statements.iter() {
-            if let ast::StatementType::Expression { ref expression } = statement.node {
-                self.codegen.emit_expr(&expression)?;
-            } else {
-                self.codegen.emit_stmt(&statement)?;
+            self.codegen.set_loc(statement.location);
+            match &statement.node {
+                ast::StatementType::Expression { ref expression } => {
+                    self.codegen.emit_expr(&expression)?;
+                }
+                ast::StatementType::Import { names } => {
+                    for symbol in names.iter() {
+                        if is_builtin_module(&symbol.symbol) {
+                            continue;
+                        }
+                        self.import_module(&symbol.symbol, statement.location)?;
+                        let alias = symbol.alias.as_ref().unwrap_or(&symbol.symbol);
+                        self.codegen.import_module(&symbol.symbol, alias);
+                    }
+                }
+                ast::StatementType::ImportFrom {
+                    level: 0,
+                    module: Some(module),
+                    names,
+                } if !is_builtin_module(module) => {
+                    self.import_module(module, statement.location)?;
+                    for symbol in names.iter() {
+                        if symbol.symbol == "*" {
+                            self.codegen.import_all(module);
+                        } else {
+                            let alias = symbol.alias.as_ref().unwrap_or(&symbol.symbol);
+                            if let Err(e) = self.codegen.import_name(module, &symbol.symbol, alias)
+                            {
+                                // `from package import module`
+                                let submodule = format!("{}.{}", module, symbol.symbol);
+                                if self.read_module(&submodule).is_none() {
+                                    return Err(e);
+                                }
+                                self.import_module(&submodule, statement.location)?;
+                                self.codegen.import_module(&submodule, alias);
+                            }
+                        }
+                    }
+                }
+                _ => self.codegen.emit_stmt(&statement)?,
             }
         }
         Ok(())
     }
 
-    /// Include and compile standard DSPython Arduino libraries
-    pub fn include_stdlib(&mut self) -> CompileResult<()> {
-        let libs = read_dir("./arduino/")
-            .expect("Failed to load DSPython Arduino core libraries")
-            .map(|res| res.map(|entry| entry.path()))
-            .collect::<Result<Vec<_>, std::io::Error>>()
-            .unwrap();
-
-        for lib in libs.iter() {
-            let lib = lib.to_str().unwrap();
-            // Skip `__init__.py` which is for linter
-            if lib.contains("__init__.py") {
-                continue;
-            }
+    /// Find a standard module in the standard libraries,
+    /// or a user module relative to the main file or in the module search paths.
+    /// Returns the path of the module with its source.
+    fn read_module(&self, module_name: &str) -> Option<(String, String)> {
+        // The pins are defined by the module of the target board
+        let module_name = if module_name == PINS_MODULE {
+            self.compiler_flags.pin_module.as_str()
+        } else {
+            module_name
+        };
+        let relative_path = module_name.replace('.', "/");
+        let candidates = vec![
+            format!("{}.py", relative_path),
+            format!("{}/__init__.py", relative_path),
+        ];
+
+        if is_stdlib_module(module_name) {
+            let stdlib_path = self.compiler_flags.stdlib_path.as_deref();
+            return candidates
+                .iter()
+                .find_map(|candidate| read_stdlib_file(stdlib_path, candidate));
+        }
 
-            let to_compile_error =
-                |parse_error| CompileError::from_parse_error(parse_error, lib.to_string());
-
-            let source =
-                read_to_string(&lib).expect(&format!("dspython: can't open file '{}'", lib));
-            let module_ast = match parse_program(&source).map_err(to_compile_error) {
-                Err(e) => {
-                    eprintln!("fatal: Could not include standard libraries");
-                    eprintln!(
-                        "An unhandled exception occurred during parsing '{}'",
-                        std::path::PathBuf::from(&e.source_path)
-                            .canonicalize()
-                            .unwrap()
-                            .display()
-                    );
-                    panic!("ParseError: {}", e);
+        let main_dir = Path::new(&self.source_path)
+            .parent()
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        let search_paths = self.compiler_flags.module_paths.iter().map(PathBuf::from);
+        for dir in std::iter::once(main_dir).chain(search_paths) {
+            for candidate in candidates.iter() {
+                let path = dir.join(candidate);
+                if path.is_file() {
+                    let path = path.to_str().unwrap().to_string();
+                    let source = read_to_string(&path)
+                        .expect(&format!("dspython: can't open file '{}'", path));
+                    return Some((path, source));
                 }
-                Ok(module) => module,
-            };
-            if let Err(mut e) = self.compile_module(module_ast) {
-                // Enrich error
-                e.file = Some(lib.to_string());
+            }
+        }
+        None
+    }
+
+    /// Parse and compile a module unless it is already compiled
+    fn import_module(&mut self, module_name: &str, location: ast::Location) -> CompileResult<()> {
+        if self.imported_modules.contains(module_name) {
+            return Ok(());
+        }
+        if let Some(index) = self.import_stack.iter().position(|m| m == module_name) {
+            let mut cycle = self.import_stack[index..].to_vec();
+            cycle.push(module_name.to_string());
+            return Err(LLVMCompileError::new(
+                Some(location),
+                LLVMCompileErrorType::ImportError(format!(
+                    "circular import: {}",
+                    cycle.join(" -> ")
+                )),
+            ));
+        }
+
+        // The registers are defined by the code generator for the target MCU
+        if module_name == REGISTERS_MODULE {
+            let previous_module = self.codegen.set_module(module_name);
+            let result = self.codegen.define_registers();
+            self.codegen.set_module(&previous_module);
+            result?;
+            self.imported_modules.insert(module_name.to_string());
+            return Ok(());
+        }
 
-                return Err(e);
+        let (path, source) = match self.read_module(module_name) {
+            Some(module) => module,
+            None => {
+                return Err(LLVMCompileError::new(
+                    Some(location),
+                    LLVMCompileErrorType::ImportError(format!("No module named '{}'", module_name)),
+                ));
+            }
+        };
+
+        let to_compile_error =
+            |parse_error| CompileError::from_parse_error(parse_error, path.clone());
+        let module_ast = match parse_program(&source).map_err(to_compile_error) {
+            Err(e) => {
+                // The embedded standard libraries do not exist on the disk
+                let source_path = PathBuf::from(&e.source_path);
+                eprintln!(
+                    "An unhandled exception occurred during parsing '{}'",
+                    source_path
+                        .canonicalize()
+                        .unwrap_or(source_path.clone())
+                        .display()
+                );
+                panic!("ParseError: {}", e);
+            }
+            Ok(module) => module,
+        };
+
+        // Definitions of the module are namespaced by the name of the module
+        self.import_stack.push(module_name.to_string());
+        let previous_module = self.codegen.set_module(module_name);
+        let result = self.compile_module(module_ast);
+        self.codegen.set_module(&previous_module);
+        self.import_stack.pop();
+
+        if let Err(mut e) = result {
+            // Enrich error
+            if e.file.is_none() {
+                e.file = Some(path);
             }
+            return Err(e);
         }
 
+        self.imported_modules.insert(module_name.to_string());
         Ok(())
     }
 
+    /// Include the builtins of the standard DSPython Arduino libraries.
+    /// The other standard modules are compiled when they are imported.
+    pub fn include_stdlib(&mut self) -> CompileResult<()> {
+        // The functions called by the code generator
+        self.import_module(WRAPPERS_MODULE, ast::Location::default())?;
+        self.import_module(BUILTINS_MODULE, ast::Location::default())
+    }
+
+    /// Check the functions declared by the stubs against the wrapper headers
+    pub fn verify_prototypes(&self) -> CompileResult<()> {
+        verify_prototypes(
+            self.codegen.module,
+            self.compiler_flags.stdlib_path.as_deref(),
+        )
+    }
+
+    /// Make the globals accessed by interrupt handlers volatile
+    pub fn mark_volatile_globals(&self) {
+        self.codegen.mark_volatile_globals();
+    }
+
     pub fn run_pm(&self) {
         self.pass_manager.run_on(&self.codegen.module);
     }
@@ -133,8 +278,31 @@ impl<'a, 'ctx> Compiler<'a, 'ctx> {
     }
 }
 
+/// The module which declares the functions called by the code generator
+const WRAPPERS_MODULE: &str = "arduino.wrappers";
+
+/// The module of the I/O registers of the target MCU
+const REGISTERS_MODULE: &str = "arduino.registers";
+
+/// The module of the pins of the target board
+const PINS_MODULE: &str = "arduino.pins";
+
+/// Modules only used by linters which are not compiled
+fn is_builtin_module(module_name: &str) -> bool {
+    ["typing", "arduino.types", "arduino.ffi"].contains(&module_name)
+}
+
+/// Modules of the standard DSPython Arduino libraries
+fn is_stdlib_module(module_name: &str) -> bool {
+    module_name == "arduino" || module_name.starts_with("arduino.")
+}
+
 /// Compile given source and return the LLVM assembly object
-pub fn get_assembly(source_path: String, flags: CompilerFlags) -> CompileResult<LLVMString> {
+/// with the names of the imported modules
+pub fn get_assembly(
+    source_path: String,
+    flags: CompilerFlags,
+) -> CompileResult<(LLVMString, Vec<String>)> {
     std::io::stdout().flush().unwrap_or_default();
 
     // Yield parse error to compile error
@@ -203,18 +371,27 @@ pub fn get_assembly(source_path: String, flags: CompilerFlags) -> CompileResult<
         ast,
     );
 
-    generate_prototypes(compiler.codegen.module, compiler.codegen.context);
     compiler.include_stdlib()?;
     if let Err(mut e) = compiler.compile() {
         // Enrich error
-        e.file = Some(compiler.source_path);
+        if e.file.is_none() {
+            e.file = Some(compiler.source_path);
+        }
 
         return Err(e);
     }
+    compiler.verify_prototypes()?;
+    compiler.mark_volatile_globals();
 
     compiler.run_pm();
     // println!("[Done]");
     {
-        Ok(compiler.emit())
+        let mut imported_modules = compiler
+            .imported_modules
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        imported_modules.sort();
+        Ok((compiler.emit(), imported_modules))
     }
 }
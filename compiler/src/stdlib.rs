@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::fs::{read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+
+use inkwell::module::Module;
+use inkwell::types::AnyType;
+
+use dsp_compiler_error::{LLVMCompileError, LLVMCompileErrorType};
+use dsp_compiler_mangler::demangle;
+use dsp_python_codegen::cgstmt::get_stub_symbols;
+use dsp_python_parser::parser::parse_program;
+
+/// The standard DSPython Arduino libraries embedded in the compiler.
+/// build.rs lists every file of `arduino`.
+const STDLIB_FILES: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/stdlib_files.rs"));
+
+/// The headers of the wrappers which declare the functions defined in them.
+/// build.rs lists every header of `include`.
+const WRAPPER_HEADERS: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/wrapper_headers.rs"));
+
+/// Read a file of the standard libraries and return its path with its source.
+/// The embedded libraries are used unless the directory of the libraries is given.
+pub fn read_stdlib_file(
+    stdlib_path: Option<&str>,
+    relative_path: &str,
+) -> Option<(String, String)> {
+    match stdlib_path {
+        Some(stdlib_path) => {
+            let path = Path::new(stdlib_path).join(relative_path);
+            let source = read_to_string(&path).ok()?;
+            Some((path.to_str().unwrap().to_string(), source))
+        }
+        None => STDLIB_FILES
+            .iter()
+            .find(|(path, _)| *path == relative_path)
+            .map(|(path, source)| (format!("<stdlib>/{}", path), source.to_string())),
+    }
+}
+
+/// Check that the functions declared by the stubs agree with the wrapper headers.
+/// Every stub of the standard libraries must have a prototype in the headers,
+/// and every prototype in the headers must be declared by a stub.
+pub fn verify_prototypes(
+    module: &Module,
+    stdlib_path: Option<&str>,
+) -> Result<(), LLVMCompileError> {
+    // The symbols declared by the stubs of every standard module, even if it is not imported
+    let mut stub_symbols = HashMap::new();
+    for (path, source) in read_stdlib_sources(stdlib_path, "arduino", "py")? {
+        let program = match parse_program(&source) {
+            Ok(program) => program,
+            Err(e) => {
+                let mut error = LLVMCompileError::new(
+                    None,
+                    LLVMCompileErrorType::SyntaxError(format!("{}", e)),
+                );
+                error.file = Some(path);
+                return Err(error);
+            }
+        };
+        stub_symbols.extend(get_stub_symbols(&program.statements));
+    }
+    // Overloaded stubs are mangled by the types of their arguments, e.g. `random__l__`
+    let is_stub = |name: &str| match stub_symbols.get(name) {
+        Some(_) => true,
+        None => matches!(demangle(name),
+            Some((origin, _)) if stub_symbols.get(origin) == Some(&true)),
+    };
+
+    let mut prototypes = HashMap::new();
+    for (header, source) in read_stdlib_sources(stdlib_path, "include", "hh")? {
+        for line in source.lines() {
+            if let Some((name, prototype)) = parse_prototype(line) {
+                if !is_stub(&name) {
+                    let mut error = LLVMCompileError::new(
+                        None,
+                        LLVMCompileErrorType::ImportError(format!(
+                            "'{}' is not declared by the stubs of the standard libraries",
+                            name
+                        )),
+                    );
+                    error.file = Some(header);
+                    return Err(error);
+                }
+                prototypes.insert(name, (header.clone(), prototype));
+            }
+        }
+    }
+
+    // The functions declared without bodies, except the ones of user C sources
+    for function in module.get_functions() {
+        let name = function.get_name().to_str().unwrap_or_default();
+        if function.count_basic_blocks() > 0 || !is_stub(name) {
+            continue;
+        }
+        let (header, expected) = match prototypes.get(name) {
+            Some(prototype) => prototype,
+            None => {
+                return Err(LLVMCompileError::new(
+                    None,
+                    LLVMCompileErrorType::ImportError(format!(
+                        "No wrapper header declares the prototype of '{}'",
+                        name
+                    )),
+                ));
+            }
+        };
+        let fn_type = function.get_type();
+        let found = format!(
+            "{} {}({})",
+            fn_type
+                .get_return_type()
+                .map(|t| t.print_to_string().to_string())
+                .unwrap_or_else(|| "void".to_string()),
+            name,
+            fn_type
+                .get_param_types()
+                .iter()
+                .map(|t| t.print_to_string().to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+        if &found != expected {
+            let mut error = LLVMCompileError::new(
+                None,
+                LLVMCompileErrorType::TypeError(expected.to_string(), found),
+            );
+            error.file = Some(header.to_string());
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+/// Read the files of a directory of the standard libraries such as the stubs and the headers.
+/// The embedded files are read unless the directory of the standard libraries is given.
+fn read_stdlib_sources(
+    stdlib_path: Option<&str>,
+    dir: &str,
+    extension: &str,
+) -> Result<Vec<(String, String)>, LLVMCompileError> {
+    let stdlib_path = match stdlib_path {
+        Some(stdlib_path) => stdlib_path,
+        None => {
+            return Ok(STDLIB_FILES
+                .iter()
+                .chain(WRAPPER_HEADERS.iter())
+                .filter(|(path, _)| {
+                    let path = Path::new(path);
+                    path.parent() == Some(Path::new(dir))
+                        && path.extension().and_then(|e| e.to_str()) == Some(extension)
+                })
+                .map(|(path, source)| (format!("<stdlib>/{}", path), source.to_string()))
+                .collect());
+        }
+    };
+
+    let dir = Path::new(stdlib_path).join(dir);
+    let to_import_error = |e: std::io::Error| {
+        LLVMCompileError::new(
+            None,
+            LLVMCompileErrorType::ImportError(format!("can't read '{}': {}", dir.display(), e)),
+        )
+    };
+    let mut paths = read_dir(&dir)
+        .map_err(to_import_error)?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some(extension))
+        .collect::<Vec<PathBuf>>();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let source = read_to_string(&path).map_err(to_import_error)?;
+            Ok((path.to_str().unwrap().to_string(), source))
+        })
+        .collect()
+}
+
+/// Parse `extern "C" int str_at(char s[], int i);` into the name
+/// and the prototype in LLVM types such as `i16 str_at(i8*, i16)`
+fn parse_prototype(line: &str) -> Option<(String, String)> {
+    let line = line
+        .trim()
+        .strip_prefix("extern \"C\"")?
+        .trim()
+        .strip_suffix(';')?;
+    let open = line.find('(')?;
+    let (signature, params) = (&line[..open], line[open + 1..].strip_suffix(')')?);
+    let separator = signature.trim().rfind(' ')?;
+    let (return_type, name) = (&signature[..separator], signature[separator + 1..].trim());
+
+    let param_types = params
+        .split(',')
+        .map(|param| param.trim())
+        .filter(|param| !param.is_empty() && *param != "void")
+        .map(|param| {
+            if param.contains('[') || param.contains('*') {
+                // Strings are the only pointers passed to the wrappers
+                return Some("i8*");
+            }
+            let separator = param.rfind(' ')?;
+            get_llvm_type_name(param[..separator].trim())
+        })
+        .collect::<Option<Vec<&str>>>()?;
+
+    let return_type = return_type.trim();
+    let prototype = format!(
+        "{} {}({})",
+        if return_type.ends_with('*') {
+            "i8*"
+        } else {
+            get_llvm_type_name(return_type)?
+        },
+        name,
+        param_types.join(", ")
+    );
+    Some((name.to_string(), prototype))
+}
+
+fn get_llvm_type_name(c_type: &str) -> Option<&'static str> {
+    match c_type {
+        "void" => Some("void"),
+        "bool" | "char" | "int8_t" | "uint8_t" => Some("i8"),
+        "int" | "unsigned int" | "int16_t" | "uint16_t" => Some("i16"),
+        "long" | "unsigned long" | "int32_t" | "uint32_t" => Some("i32"),
+        // double is as wide as float in avr-gcc
+        "float" | "double" => Some("float"),
+        // `void (*)(void)` defined in wiring_private.h
+        "voidFuncPtr" => Some("void ()*"),
+        _ => None,
+    }
+}
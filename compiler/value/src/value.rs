@@ -26,6 +26,7 @@ pub enum Value<'ctx> {
     F32 { value: FloatValue<'ctx> },
     F64 { value: FloatValue<'ctx> },
     Str { value: PointerValue<'ctx> },
+    Function { value: PointerValue<'ctx> },
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -47,6 +48,7 @@ pub enum ValueType {
     F32,
     F64,
     Str,
+    Function,
 }
 
 #[derive(Debug, PartialEq)]
@@ -58,6 +60,7 @@ pub enum ValueTypeGroup {
     UInt,
     Float,
     Str,
+    Function,
 }
 
 pub struct ValueHandler<'cb, 'ctx: 'cb, T> {
@@ -68,6 +71,7 @@ pub struct ValueHandler<'cb, 'ctx: 'cb, T> {
     unsigned_int_handler: &'cb dyn Fn(&Value<'ctx>, IntValue<'ctx>) -> T,
     float_handler: &'cb dyn Fn(&Value<'ctx>, FloatValue<'ctx>) -> T,
     str_handler: &'cb dyn Fn(&Value<'ctx>, PointerValue<'ctx>) -> T,
+    function_handler: &'cb dyn Fn(&Value<'ctx>, PointerValue<'ctx>) -> T,
 }
 
 pub struct ValueTypeHandler<'cb, 'ctx: 'cb, T> {
@@ -78,6 +82,7 @@ pub struct ValueTypeHandler<'cb, 'ctx: 'cb, T> {
     unsigned_int_handler: &'cb dyn Fn(&ValueType, IntType<'ctx>) -> T,
     float_handler: &'cb dyn Fn(&ValueType, FloatType<'ctx>) -> T,
     str_handler: &'cb dyn Fn(&ValueType, PointerType<'ctx>) -> T,
+    function_handler: &'cb dyn Fn(&ValueType, PointerType<'ctx>) -> T,
 }
 
 impl<'cb, 'ctx: 'cb, T> ValueHandler<'cb, 'ctx, T> {
@@ -90,6 +95,7 @@ impl<'cb, 'ctx: 'cb, T> ValueHandler<'cb, 'ctx, T> {
             unsigned_int_handler: &|_, _| panic!("wrong type; unsigned int type is not allowed."),
             float_handler: &|_, _| panic!("wrong type; float type is not allowed."),
             str_handler: &|_, _| panic!("wrong type; str type is not allowed."),
+            function_handler: &|_, _| panic!("wrong type; function type is not allowed."),
         }
     }
 
@@ -137,6 +143,14 @@ impl<'cb, 'ctx: 'cb, T> ValueHandler<'cb, 'ctx, T> {
         self.str_handler = handler;
         self
     }
+
+    pub fn handle_function(
+        &mut self,
+        handler: &'cb dyn Fn(&Value<'ctx>, PointerValue<'ctx>) -> T,
+    ) -> &mut Self {
+        self.function_handler = handler;
+        self
+    }
 }
 
 impl<'cb, 'ctx: 'cb, T> ValueTypeHandler<'cb, 'ctx, T> {
@@ -149,6 +163,7 @@ impl<'cb, 'ctx: 'cb, T> ValueTypeHandler<'cb, 'ctx, T> {
             unsigned_int_handler: &|_, _| panic!("wrong type; unsigned int type is not allowed."),
             float_handler: &|_, _| panic!("wrong type; float type is not allowed."),
             str_handler: &|_, _| panic!("wrong type; str type is not allowed."),
+            function_handler: &|_, _| panic!("wrong type; function type is not allowed."),
         }
     }
 
@@ -199,6 +214,14 @@ impl<'cb, 'ctx: 'cb, T> ValueTypeHandler<'cb, 'ctx, T> {
         self.str_handler = handler;
         self
     }
+
+    pub fn handle_function(
+        &mut self,
+        handler: &'cb dyn Fn(&ValueType, PointerType<'ctx>) -> T,
+    ) -> &mut Self {
+        self.function_handler = handler;
+        self
+    }
 }
 
 impl<'ctx> Value<'ctx> {
@@ -221,6 +244,7 @@ impl<'ctx> Value<'ctx> {
             Value::F32 { value: _ } => ValueType::F32,
             Value::F64 { value: _ } => ValueType::F64,
             Value::Str { value: _ } => ValueType::Str,
+            Value::Function { value: _ } => ValueType::Function,
         }
     }
 
@@ -285,6 +309,7 @@ impl<'ctx> Value<'ctx> {
             },
             AnyValueEnum::PointerValue(value) => match value_type {
                 ValueType::Str => Value::Str { value },
+                ValueType::Function => Value::Function { value },
                 _ => panic!(
                     "value type mismatch; given value is not instance of {:?}",
                     value_type
@@ -330,6 +355,7 @@ impl<'ctx> Value<'ctx> {
             },
             BasicValueEnum::PointerValue(value) => match value_type {
                 ValueType::Str => Value::Str { value },
+                ValueType::Function => Value::Function { value },
                 _ => panic!(
                     "value type mismatch; given value is not instance of {:?}",
                     value_type
@@ -358,6 +384,7 @@ impl<'ctx> Value<'ctx> {
             Value::F32 { value } => AnyValueEnum::FloatValue(*value),
             Value::F64 { value } => AnyValueEnum::FloatValue(*value),
             Value::Str { value } => AnyValueEnum::PointerValue(*value),
+            Value::Function { value } => AnyValueEnum::PointerValue(*value),
         }
     }
 
@@ -380,6 +407,7 @@ impl<'ctx> Value<'ctx> {
             Value::F32 { value } => BasicValueEnum::FloatValue(*value),
             Value::F64 { value } => BasicValueEnum::FloatValue(*value),
             Value::Str { value } => BasicValueEnum::PointerValue(*value),
+            Value::Function { value } => BasicValueEnum::PointerValue(*value),
         }
     }
 
@@ -402,11 +430,31 @@ impl<'ctx> Value<'ctx> {
             Value::F32 { value } => (*value_handler.float_handler)(self, *value),
             Value::F64 { value } => (*value_handler.float_handler)(self, *value),
             Value::Str { value } => (*value_handler.str_handler)(self, *value),
+            Value::Function { value } => (*value_handler.function_handler)(self, *value),
         }
     }
 }
 
 impl ValueType {
+    /// Get the type from the name used in type hints
+    pub fn from_annotation(name: &str) -> Option<ValueType> {
+        match name {
+            "None" => Some(ValueType::Void),
+            "bool" => Some(ValueType::Bool),
+            "int8" => Some(ValueType::I8),
+            "int" => Some(ValueType::I16),
+            "int32" => Some(ValueType::I32),
+            "uint8" => Some(ValueType::U8),
+            "uint16" => Some(ValueType::U16),
+            "uint32" => Some(ValueType::U32),
+            "float" => Some(ValueType::F32),
+            "str" => Some(ValueType::Str),
+            // Only `Callable[[], None]` is supported
+            "Callable" => Some(ValueType::Function),
+            _ => None,
+        }
+    }
+
     pub fn is_void(&self) -> bool {
         match self {
             ValueType::Void => true,
@@ -414,6 +462,14 @@ impl ValueType {
         }
     }
 
+    /// Booleans are integers in arithmetic operations like Python
+    pub fn is_integer(&self) -> bool {
+        match self.get_group() {
+            ValueTypeGroup::Bool | ValueTypeGroup::Int | ValueTypeGroup::UInt => true,
+            _ => false,
+        }
+    }
+
     pub fn to_any_type<'ctx>(&self, context: &'ctx Context) -> AnyTypeEnum<'ctx> {
         match self {
             ValueType::Void => AnyTypeEnum::VoidType(context.void_type()),
@@ -435,6 +491,7 @@ impl ValueType {
             ValueType::Str => {
                 AnyTypeEnum::PointerType(context.i8_type().ptr_type(AddressSpace::Generic))
             }
+            ValueType::Function => AnyTypeEnum::PointerType(get_function_pointer_type(context)),
         }
     }
 
@@ -459,6 +516,7 @@ impl ValueType {
             ValueType::Str => {
                 BasicTypeEnum::PointerType(context.i8_type().ptr_type(AddressSpace::Generic))
             }
+            ValueType::Function => BasicTypeEnum::PointerType(get_function_pointer_type(context)),
         }
     }
 
@@ -481,6 +539,7 @@ impl ValueType {
             ValueType::F32 => ValueTypeGroup::Float,
             ValueType::F64 => ValueTypeGroup::Float,
             ValueType::Str => ValueTypeGroup::Str,
+            ValueType::Function => ValueTypeGroup::Function,
         }
     }
 
@@ -502,7 +561,7 @@ impl ValueType {
             ValueType::F16 => 16,
             ValueType::F32 => 32,
             ValueType::F64 => 64,
-            ValueType::Str => 0, //Unknown
+            ValueType::Str | ValueType::Function => 0, //Unknown
         }
     }
 
@@ -534,6 +593,9 @@ impl ValueType {
                 self,
                 context.i8_type().ptr_type(AddressSpace::Generic),
             ),
+            ValueType::Function => {
+                (*value_type_handler.function_handler)(self, get_function_pointer_type(context))
+            }
         }
     }
 
@@ -549,3 +611,11 @@ impl ValueType {
         }
     }
 }
+
+/// The type of `void (*)(void)` functions such as interrupt handlers
+pub fn get_function_pointer_type(context: &Context) -> PointerType {
+    context
+        .void_type()
+        .fn_type(&[], false)
+        .ptr_type(AddressSpace::Generic)
+}
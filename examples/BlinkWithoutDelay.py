@@ -0,0 +1,54 @@
+"""
+This is the DSPython implementation of the Arduino example.
+
+The original code is:
+
+/*
+  Blink without Delay
+
+  Turns on and off a light emitting diode (LED) connected to a digital pin,
+  without using the delay() function. This means that other code can run at the
+  same time without being interrupted by the LED code.
+
+  created 2005
+  by David A. Mellis
+  modified 8 Feb 2010
+  by Paul Stoffregen
+  modified 11 Nov 2013
+  by Scott Fitzgerald
+  modified 9 Jan 2017
+  by Arturo Guadalupi
+
+  This example code is in the public domain.
+
+  http://www.arduino.cc/en/Tutorial/BlinkWithoutDelay
+*/
+"""
+
+from arduino import *
+
+led_state: uint8 = LOW
+# The last time the LED was updated
+previous_millis: uint32 = 0
+# The interval at which to blink (milliseconds)
+interval: uint32 = 1000
+
+
+def setup():
+    pin_mode(LED_BUILTIN, OUTPUT)
+
+
+def loop():
+    global led_state, previous_millis
+
+    current_millis = millis()
+    # The unsigned subtraction is correct even after millis() wraps around
+    if current_millis - previous_millis >= interval:
+        previous_millis = current_millis
+
+        if led_state == LOW:
+            led_state = HIGH
+        else:
+            led_state = LOW
+
+        digital_write(LED_BUILTIN, led_state)
@@ -29,6 +29,4 @@ def loop():
         analog_write(pin_green, int(g))
         analog_write(pin_blue, int(b))
 
-        print(int(r)); print(",")
-        print(int(g)); print(",")
-        println(int(b))
+        println(f"{int(r)},{int(g)},{int(b)}")
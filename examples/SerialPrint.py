@@ -9,7 +9,7 @@ from arduino import *
 
 def setup():
     serial_begin(9600)
-    print("Hello, Arduino!")
+    print("Hello, Arduino!", end=" ")
     println("...from Damn Small Python")
 
 
@@ -15,3 +15,33 @@ extern "C" float float__f__(float n) {
 extern "C" float float__i__(int n) {
     return (float)n;
 }
+
+extern "C" long random__l__(long max) {
+    return random(max);
+}
+
+extern "C" long random__l_l__(long low, long high) {
+    return random(low, high);
+}
+
+extern "C" float random__f__(float max) {
+    return random__f_f__(0, max);
+}
+
+extern "C" float random__f_f__(float low, float high) {
+    // random() of avr-libc returns a number from 0 to RANDOM_MAX
+    return low + (high - low) * ((float)::random() / RANDOM_MAX);
+}
+
+extern "C" void random_seed(unsigned long seed) {
+    randomSeed(seed);
+}
+
+// The variables annotated with Persistent are copied between SRAM and the EEPROM
+extern "C" void persistent_read(void* value, int address, uint8_t size) {
+    eeprom_read_block(value, (const void*)address, size);
+}
+
+extern "C" void persistent_write(void* value, int address, uint8_t size) {
+    eeprom_update_block(value, (void*)address, size);
+}
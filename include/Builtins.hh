@@ -1,6 +1,14 @@
 #include <Arduino.h>
+#include <avr/eeprom.h>
 
 extern "C" int int__f__(float n);
 extern "C" int int__i__(int n);
 extern "C" float float__f__(float n);
 extern "C" float float__i__(int n);
+extern "C" long random__l__(long max);
+extern "C" long random__l_l__(long low, long high);
+extern "C" float random__f__(float max);
+extern "C" float random__f_f__(float low, float high);
+extern "C" void random_seed(unsigned long seed);
+extern "C" void persistent_read(void* value, int address, uint8_t size);
+extern "C" void persistent_write(void* value, int address, uint8_t size);
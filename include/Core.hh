@@ -0,0 +1,31 @@
+#include <Arduino.h>
+#include <math.h>
+
+// The functions of the Arduino core and avr-libc which the stubs declare without wrappers.
+// This header is only read to check the stubs, and double is as wide as float in avr-gcc.
+extern "C" void delay(unsigned long ms);
+extern "C" unsigned long millis();
+extern "C" unsigned long micros();
+extern "C" double sin(double x);
+extern "C" double cos(double x);
+extern "C" double tan(double x);
+extern "C" double asin(double x);
+extern "C" double acos(double x);
+extern "C" double atan(double x);
+extern "C" double atan2(double y, double x);
+extern "C" double sinh(double x);
+extern "C" double cosh(double x);
+extern "C" double tanh(double x);
+extern "C" double sqrt(double x);
+extern "C" double cbrt(double x);
+extern "C" double hypot(double x, double y);
+extern "C" double exp(double x);
+extern "C" double log(double x);
+extern "C" double log10(double x);
+extern "C" double fabs(double x);
+extern "C" double fmod(double x, double y);
+extern "C" double pow(double x, double y);
+extern "C" double floor(double x);
+extern "C" double ceil(double x);
+extern "C" double trunc(double x);
+extern "C" long lrint(double x);
@@ -0,0 +1,6 @@
+#include "EEPROM.hh"
+
+extern "C" uint8_t eeprom_read(int address) { return EEPROM.read(address); }
+extern "C" void eeprom_write(int address, uint8_t value) { EEPROM.write(address, value); }
+extern "C" void eeprom_update(int address, uint8_t value) { EEPROM.update(address, value); }
+extern "C" int eeprom_length() { return EEPROM.length(); }
@@ -0,0 +1,7 @@
+#include <Arduino.h>
+#include <EEPROM.h>
+
+extern "C" uint8_t eeprom_read(int address);
+extern "C" void eeprom_write(int address, uint8_t value);
+extern "C" void eeprom_update(int address, uint8_t value);
+extern "C" int eeprom_length();
@@ -4,8 +4,53 @@ extern "C" void pin_mode(uint8_t pin, uint8_t mode) {
     return pinMode(pin, mode);
 }
 
-extern "C" float pulse_in(uint8_t pin, uint8_t mode) {
-    return pulseIn(pin, mode);
+extern "C" unsigned long pulse_in__u_u__(uint8_t pin, uint8_t state) {
+    return pulseIn(pin, state);
+}
+
+extern "C" unsigned long pulse_in__u_u_ul__(uint8_t pin, uint8_t state, unsigned long timeout) {
+    return pulseIn(pin, state, timeout);
+}
+
+extern "C" void tone__u_u__(uint8_t pin, unsigned int frequency) {
+    return tone(pin, frequency);
+}
+
+extern "C" void tone__u_u_ul__(uint8_t pin, unsigned int frequency, unsigned long duration) {
+    return tone(pin, frequency, duration);
+}
+
+extern "C" void no_tone(uint8_t pin) {
+    return noTone(pin);
+}
+
+extern "C" void shift_out(uint8_t data_pin, uint8_t clock_pin, uint8_t bit_order, uint8_t value) {
+    return shiftOut(data_pin, clock_pin, bit_order, value);
+}
+
+extern "C" uint8_t shift_in(uint8_t data_pin, uint8_t clock_pin, uint8_t bit_order) {
+    return shiftIn(data_pin, clock_pin, bit_order);
+}
+
+extern "C" void delay_microseconds(unsigned int us) {
+    return delayMicroseconds(us);
+}
+
+extern "C" void attach_interrupt(uint8_t pin, voidFuncPtr handler, uint8_t mode) {
+    return attachInterrupt(digitalPinToInterrupt(pin), handler, mode);
+}
+
+extern "C" void detach_interrupt(uint8_t pin) {
+    return detachInterrupt(digitalPinToInterrupt(pin));
+}
+
+// interrupts() and noInterrupts() are macros
+extern "C" void enable_interrupts(void) {
+    sei();
+}
+
+extern "C" void disable_interrupts(void) {
+    cli();
 }
 
 extern "C" void digital_write(uint8_t pin, uint8_t val) {
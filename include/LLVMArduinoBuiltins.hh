@@ -2,7 +2,18 @@
 #include <wiring_private.h>
 
 extern "C" void pin_mode(uint8_t pin, uint8_t mode);
-extern "C" float pulse_in(uint8_t pin, uint8_t mode);
+extern "C" unsigned long pulse_in__u_u__(uint8_t pin, uint8_t state);
+extern "C" unsigned long pulse_in__u_u_ul__(uint8_t pin, uint8_t state, unsigned long timeout);
+extern "C" void tone__u_u__(uint8_t pin, unsigned int frequency);
+extern "C" void tone__u_u_ul__(uint8_t pin, unsigned int frequency, unsigned long duration);
+extern "C" void no_tone(uint8_t pin);
+extern "C" void shift_out(uint8_t data_pin, uint8_t clock_pin, uint8_t bit_order, uint8_t value);
+extern "C" uint8_t shift_in(uint8_t data_pin, uint8_t clock_pin, uint8_t bit_order);
+extern "C" void delay_microseconds(unsigned int us);
+extern "C" void attach_interrupt(uint8_t pin, voidFuncPtr handler, uint8_t mode);
+extern "C" void detach_interrupt(uint8_t pin);
+extern "C" void enable_interrupts(void);
+extern "C" void disable_interrupts(void);
 extern "C" void digital_write(uint8_t pin, uint8_t val);
 extern "C" void analog_write(uint8_t pin, uint8_t val);
 extern "C" int digital_read(uint8_t pin);
@@ -0,0 +1,9 @@
+#include "SPI.hh"
+
+extern "C" void spi_begin() { SPI.begin(); }
+extern "C" void spi_end() { SPI.end(); }
+extern "C" void spi_begin_transaction(uint32_t clock, uint8_t bit_order, uint8_t data_mode) {
+    SPI.beginTransaction(SPISettings(clock, bit_order, data_mode));
+}
+extern "C" void spi_end_transaction() { SPI.endTransaction(); }
+extern "C" uint8_t spi_transfer(uint8_t data) { return SPI.transfer(data); }
@@ -0,0 +1,8 @@
+#include <Arduino.h>
+#include <SPI.h>
+
+extern "C" void spi_begin();
+extern "C" void spi_end();
+extern "C" void spi_begin_transaction(uint32_t clock, uint8_t bit_order, uint8_t data_mode);
+extern "C" void spi_end_transaction();
+extern "C" uint8_t spi_transfer(uint8_t data);
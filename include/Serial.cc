@@ -1,12 +1,124 @@
 #include "Serial.hh"
 
-extern "C" void print__i__(int n) { Serial.print(n); }
-extern "C" void print__f__(float n) { Serial.print(n); }
-extern "C" void print__s__(char c[]) { Serial.print(c); }
-extern "C" void println__i__(int n) { Serial.println(n); }
-extern "C" void println__f__(float n) { Serial.println(n); }
-extern "C" void println__s__(char c[]) { Serial.println(c); }
+struct SerialPort {
+    Stream* stream;
+    void (*begin)(Stream*, unsigned long);
+};
+
+// The ports are referred by their index from Python. Serial is always the port 0.
+// The other hardware ports are set when they begin so that they are only linked when used.
+static SerialPort ports[MAX_SERIAL_PORTS] = {
+    { &Serial, begin_port<decltype(Serial)> },
+};
+static uint8_t port_count = 4;
+
+// Discards the output printed to a port which does not exist or has not begun
+class NullOutput : public Print {
+public:
+    size_t write(uint8_t) override { return 1; }
+};
+static NullOutput null_output;
+
+// The port written by the print functions
+static Print* output = &Serial;
+
+static Stream* get_port(uint8_t port) {
+    return port < port_count ? ports[port].stream : nullptr;
+}
+
+uint8_t serial_port_add(Stream* stream, void (*begin)(Stream*, unsigned long)) {
+    if (port_count == MAX_SERIAL_PORTS) return INVALID_SERIAL_PORT;
+    ports[port_count] = { stream, begin };
+    return port_count++;
+}
+
+void serial_port_set(uint8_t port, Stream* stream, void (*begin)(Stream*, unsigned long)) {
+    if (port < port_count) ports[port] = { stream, begin };
+}
+
+extern "C" void print__i__(int n) { output->print(n); }
+extern "C" void print__f__(float n) { output->print(n); }
+extern "C" void print__s__(char c[]) { output->print(c); }
+// The string is in the program memory like the ones made by F()
+extern "C" void print_flash(const char s[]) { output->print(reinterpret_cast<const __FlashStringHelper*>(s)); }
+extern "C" void print__b__(uint8_t b) { output->print(b ? "True" : "False"); }
+extern "C" void print__l__(long n) { output->print(n); }
+extern "C" void print__u__(unsigned int n) { output->print(n); }
+extern "C" void print__ul__(unsigned long n) { output->print(n); }
+extern "C" void print_base__ul__(unsigned long n, uint8_t base, uint8_t upper) {
+    char buf[8 * sizeof(long) + 1];
+    ultoa(n, buf, base);
+    if (upper) strupr(buf);
+    output->print(buf);
+}
+extern "C" void print_base__l__(long n, uint8_t base, uint8_t upper) {
+    // Negative numbers are printed with a sign like Python does
+    unsigned long u = n;
+    if (n < 0) {
+        output->print('-');
+        u = -u;
+    }
+    print_base__ul__(u, base, upper);
+}
+extern "C" void print_digits__f__(float n, uint8_t digits) { output->print(n, digits); }
+extern "C" int serial_available() { return Serial.available(); }
 extern "C" int is_serial_available() { return Serial.available(); }
 extern "C" void serial_begin(int b) { Serial.begin(b); }
 extern "C" int input() { return Serial.read(); }
-extern "C" void flush() { return Serial.flush(); }
\ No newline at end of file
+extern "C" void flush() { return Serial.flush(); }
+extern "C" int serial_read() { return Serial.read(); }
+extern "C" int serial_peek() { return Serial.peek(); }
+extern "C" void serial_set_timeout(unsigned long timeout) { Serial.setTimeout(timeout); }
+extern "C" char* serial_read_line(int buffer_size) { return serial_port_read_line(0, buffer_size); }
+extern "C" long serial_parse_int() { return Serial.parseInt(); }
+extern "C" float serial_parse_float() { return Serial.parseFloat(); }
+extern "C" void print_select(uint8_t port) {
+    Stream* stream = get_port(port);
+    output = stream ? static_cast<Print*>(stream) : &null_output;
+}
+extern "C" void serial_port_begin(uint8_t port, unsigned long baudrate) {
+    if (get_port(port)) ports[port].begin(ports[port].stream, baudrate);
+}
+extern "C" int serial_port_available(uint8_t port) {
+    Stream* stream = get_port(port);
+    return stream ? stream->available() : 0;
+}
+extern "C" int serial_port_read(uint8_t port) {
+    Stream* stream = get_port(port);
+    return stream ? stream->read() : -1;
+}
+extern "C" int serial_port_peek(uint8_t port) {
+    Stream* stream = get_port(port);
+    return stream ? stream->peek() : -1;
+}
+extern "C" void serial_port_write(uint8_t port, uint8_t b) {
+    Stream* stream = get_port(port);
+    if (stream) stream->write(b);
+}
+extern "C" void serial_port_flush(uint8_t port) {
+    Stream* stream = get_port(port);
+    if (stream) stream->flush();
+}
+extern "C" void serial_port_set_timeout(uint8_t port, unsigned long timeout) {
+    Stream* stream = get_port(port);
+    if (stream) stream->setTimeout(timeout);
+}
+extern "C" char* serial_port_read_line(uint8_t port, int buffer_size) {
+    // The line is kept until the next call
+    static char line[SERIAL_LINE_SIZE];
+    if (buffer_size > SERIAL_LINE_SIZE || buffer_size <= 0) buffer_size = SERIAL_LINE_SIZE;
+
+    Stream* stream = get_port(port);
+    size_t length = stream ? stream->readBytesUntil('\n', line, buffer_size - 1) : 0;
+    if (length > 0 && line[length - 1] == '\r') length--;
+    line[length] = '\0';
+    return line;
+}
+extern "C" long serial_port_parse_int(uint8_t port) {
+    Stream* stream = get_port(port);
+    return stream ? stream->parseInt() : 0;
+}
+extern "C" float serial_port_parse_float(uint8_t port) {
+    Stream* stream = get_port(port);
+    return stream ? stream->parseFloat() : 0;
+}
@@ -2,13 +2,57 @@
 #include "Print.h"
 #include <HardwareSerial.h>
 
+// The longest line read by serial_read_line including the terminator
+#define SERIAL_LINE_SIZE 64
+// The hardware ports followed by the software ports
+#define MAX_SERIAL_PORTS 8
+// The handle returned when no more ports can be added
+#define INVALID_SERIAL_PORT 0xFF
+
+// Calls the begin method of the class of a port which Stream does not declare
+template <class T>
+static void begin_port(Stream* stream, unsigned long baudrate) {
+    static_cast<T*>(stream)->begin(baudrate);
+}
+
+// Add a port such as a SoftwareSerial and return its handle
+uint8_t serial_port_add(Stream* stream, void (*begin)(Stream*, unsigned long));
+// Set a hardware port such as Serial1 when it begins
+void serial_port_set(uint8_t port, Stream* stream, void (*begin)(Stream*, unsigned long));
+
 extern "C" void print__i__(int n);
 extern "C" void print__f__(float n);
 extern "C" void print__s__(char c[]);
-extern "C" void println__i__(int n);
-extern "C" void println__f__(float n);
-extern "C" void println__s__(char c[]);
+extern "C" void print_flash(const char s[]);
+extern "C" void print__b__(uint8_t b);
+extern "C" void print__l__(long n);
+extern "C" void print__u__(unsigned int n);
+extern "C" void print__ul__(unsigned long n);
+extern "C" void print_base__l__(long n, uint8_t base, uint8_t upper);
+extern "C" void print_base__ul__(unsigned long n, uint8_t base, uint8_t upper);
+extern "C" void print_digits__f__(float n, uint8_t digits);
+extern "C" int serial_available();
 extern "C" int is_serial_available();
 extern "C" void serial_begin(int b);
 extern "C" int input();
 extern "C" void flush();
+extern "C" int serial_read();
+extern "C" int serial_peek();
+extern "C" void serial_set_timeout(unsigned long timeout);
+extern "C" char* serial_read_line(int buffer_size);
+extern "C" long serial_parse_int();
+extern "C" float serial_parse_float();
+extern "C" void print_select(uint8_t port);
+extern "C" void serial_port_begin(uint8_t port, unsigned long baudrate);
+extern "C" void serial1_begin(uint8_t port, unsigned long baudrate);
+extern "C" void serial2_begin(uint8_t port, unsigned long baudrate);
+extern "C" void serial3_begin(uint8_t port, unsigned long baudrate);
+extern "C" int serial_port_available(uint8_t port);
+extern "C" int serial_port_read(uint8_t port);
+extern "C" int serial_port_peek(uint8_t port);
+extern "C" void serial_port_write(uint8_t port, uint8_t b);
+extern "C" void serial_port_flush(uint8_t port);
+extern "C" void serial_port_set_timeout(uint8_t port, unsigned long timeout);
+extern "C" char* serial_port_read_line(uint8_t port, int buffer_size);
+extern "C" long serial_port_parse_int(uint8_t port);
+extern "C" float serial_port_parse_float(uint8_t port);
@@ -0,0 +1,9 @@
+#include "Serial.hh"
+
+// Serial1 with its buffers and interrupt handlers is only linked with the programs which begin it
+extern "C" void serial1_begin(uint8_t port, unsigned long baudrate) {
+#if defined(HAVE_HWSERIAL1)
+    serial_port_set(port, &Serial1, begin_port<HardwareSerial>);
+#endif
+    serial_port_begin(port, baudrate);
+}
@@ -0,0 +1,9 @@
+#include "Serial.hh"
+
+// Serial2 with its buffers and interrupt handlers is only linked with the programs which begin it
+extern "C" void serial2_begin(uint8_t port, unsigned long baudrate) {
+#if defined(HAVE_HWSERIAL2)
+    serial_port_set(port, &Serial2, begin_port<HardwareSerial>);
+#endif
+    serial_port_begin(port, baudrate);
+}
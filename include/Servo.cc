@@ -0,0 +1,29 @@
+#include "Servo.hh"
+
+// Servos are referred by their index from Python. The calls with an invalid index do nothing.
+static Servo servos[MAX_SERVOS];
+
+extern "C" void servo_attach(uint8_t servo, uint8_t pin) {
+    if (servo >= MAX_SERVOS) return;
+    servos[servo].attach(pin);
+}
+extern "C" void servo_detach(uint8_t servo) {
+    if (servo >= MAX_SERVOS) return;
+    servos[servo].detach();
+}
+extern "C" void servo_write(uint8_t servo, int angle) {
+    if (servo >= MAX_SERVOS) return;
+    servos[servo].write(angle);
+}
+extern "C" void servo_write_microseconds(uint8_t servo, int us) {
+    if (servo >= MAX_SERVOS) return;
+    servos[servo].writeMicroseconds(us);
+}
+extern "C" int servo_read(uint8_t servo) {
+    if (servo >= MAX_SERVOS) return 0;
+    return servos[servo].read();
+}
+extern "C" uint8_t servo_attached(uint8_t servo) {
+    if (servo >= MAX_SERVOS) return false;
+    return servos[servo].attached();
+}
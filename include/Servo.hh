@@ -0,0 +1,9 @@
+#include <Arduino.h>
+#include <Servo.h>
+
+extern "C" void servo_attach(uint8_t servo, uint8_t pin);
+extern "C" void servo_detach(uint8_t servo);
+extern "C" void servo_write(uint8_t servo, int angle);
+extern "C" void servo_write_microseconds(uint8_t servo, int us);
+extern "C" int servo_read(uint8_t servo);
+extern "C" uint8_t servo_attached(uint8_t servo);
@@ -0,0 +1,9 @@
+#include "SoftwareSerial.hh"
+
+// The ports live until the board is reset
+extern "C" uint8_t software_serial_new(uint8_t rx, uint8_t tx) {
+    SoftwareSerial* port = new SoftwareSerial(rx, tx);
+    uint8_t handle = serial_port_add(port, begin_port<SoftwareSerial>);
+    if (handle == INVALID_SERIAL_PORT) delete port;
+    return handle;
+}
@@ -0,0 +1,5 @@
+#include <Arduino.h>
+#include <SoftwareSerial.h>
+#include "Serial.hh"
+
+extern "C" uint8_t software_serial_new(uint8_t rx, uint8_t tx);
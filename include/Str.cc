@@ -0,0 +1,27 @@
+#include "Str.hh"
+
+extern "C" void str_concat(char dst[], char a[], char b[], int size) {
+    int len_a = strlen(a);
+    if (len_a > size - 1) len_a = size - 1;
+    int len_b = strlen(b);
+    if (len_b > size - 1 - len_a) len_b = size - 1 - len_a;
+
+    // `dst` may be the same buffer as `a` or `b`
+    memmove(dst + len_a, b, len_b);
+    if (dst != a) memmove(dst, a, len_a);
+    dst[len_a + len_b] = '\0';
+}
+
+extern "C" int str_compare(char a[], char b[]) {
+    int res = strcmp(a, b);
+    return res < 0 ? -1 : res > 0 ? 1 : 0;
+}
+
+extern "C" int str_at(char s[], int i) {
+    int len = strlen(s);
+    if (i < 0) i += len;
+    if (i < 0 || i >= len) return -1;
+    return (unsigned char)s[i];
+}
+
+extern "C" int len__s__(char s[]) { return strlen(s); }
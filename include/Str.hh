@@ -0,0 +1,6 @@
+#include <Arduino.h>
+
+extern "C" void str_concat(char dst[], char a[], char b[], int size);
+extern "C" int str_compare(char a[], char b[]);
+extern "C" int str_at(char s[], int i);
+extern "C" int len__s__(char s[]);
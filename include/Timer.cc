@@ -0,0 +1,46 @@
+#include "Timer.hh"
+
+static const uint16_t prescalers[] = {1, 8, 64, 256, 1024};
+
+extern "C" void timer1_start(uint16_t prescaler, uint16_t compare) {
+    uint8_t clock_select;
+    switch (prescaler) {
+        case 1: clock_select = _BV(CS10); break;
+        case 8: clock_select = _BV(CS11); break;
+        case 64: clock_select = _BV(CS11) | _BV(CS10); break;
+        case 256: clock_select = _BV(CS12); break;
+        case 1024: clock_select = _BV(CS12) | _BV(CS10); break;
+        default: return;
+    }
+
+    uint8_t sreg = SREG;
+    cli();
+    TCCR1A = 0;
+    // Clear the counter on compare match
+    TCCR1B = _BV(WGM12) | clock_select;
+    TCNT1 = 0;
+    OCR1A = compare;
+    TIMSK1 |= _BV(OCIE1A);
+    SREG = sreg;
+}
+
+extern "C" void timer1_set_frequency(uint32_t frequency) {
+    if (frequency == 0) {
+        return timer1_stop();
+    }
+
+    // The smallest prescaler gives the best resolution
+    for (uint8_t i = 0; i < sizeof(prescalers) / sizeof(prescalers[0]); i++) {
+        uint32_t ticks = F_CPU / prescalers[i] / frequency;
+        if (ticks <= 65536) {
+            return timer1_start(prescalers[i], ticks > 0 ? ticks - 1 : 0);
+        }
+    }
+    // The lowest frequency
+    timer1_start(1024, 65535);
+}
+
+extern "C" void timer1_stop() {
+    TIMSK1 &= ~_BV(OCIE1A);
+    TCCR1B = 0;
+}
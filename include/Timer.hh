@@ -0,0 +1,5 @@
+#include <Arduino.h>
+
+extern "C" void timer1_start(uint16_t prescaler, uint16_t compare);
+extern "C" void timer1_set_frequency(uint32_t frequency);
+extern "C" void timer1_stop();
@@ -0,0 +1,11 @@
+#include "Wire.hh"
+
+extern "C" void wire_begin() { Wire.begin(); }
+extern "C" void wire_end() { Wire.end(); }
+extern "C" void wire_set_clock(uint32_t frequency) { Wire.setClock(frequency); }
+extern "C" void wire_begin_transmission(uint8_t address) { Wire.beginTransmission(address); }
+extern "C" uint8_t wire_end_transmission() { return Wire.endTransmission(); }
+extern "C" uint8_t wire_request_from(uint8_t address, uint8_t quantity) { return Wire.requestFrom(address, quantity); }
+extern "C" int wire_write(uint8_t data) { return Wire.write(data); }
+extern "C" int wire_available() { return Wire.available(); }
+extern "C" int wire_read() { return Wire.read(); }
@@ -0,0 +1,12 @@
+#include <Arduino.h>
+#include <Wire.h>
+
+extern "C" void wire_begin();
+extern "C" void wire_end();
+extern "C" void wire_set_clock(uint32_t frequency);
+extern "C" void wire_begin_transmission(uint8_t address);
+extern "C" uint8_t wire_end_transmission();
+extern "C" uint8_t wire_request_from(uint8_t address, uint8_t quantity);
+extern "C" int wire_write(uint8_t data);
+extern "C" int wire_available();
+extern "C" int wire_read();
@@ -1,14 +1,20 @@
+use std::cmp::Ordering;
+
 use either::Either;
-use inkwell::values::{BasicValueEnum, InstructionOpcode};
-use inkwell::{FloatPredicate, IntPredicate};
+use inkwell::values::{BasicValue, BasicValueEnum, FunctionValue};
+use inkwell::{AddressSpace, FloatPredicate, IntPredicate};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
 
 use dsp_compiler_error::{err, LLVMCompileError, LLVMCompileErrorType};
-use dsp_compiler_mangler::get_mangled_name;
-use dsp_compiler_value::convert::{truncate_bigint_to_u64, try_get_constant_string};
-use dsp_compiler_value::value::{Value, ValueHandler, ValueType};
+use dsp_compiler_mangler::{resolve_overload, OverloadError};
+use dsp_compiler_value::convert::try_get_constant_string;
+use dsp_compiler_value::value::{
+    get_function_pointer_type, Value, ValueHandler, ValueType, ValueTypeGroup,
+};
 use dsp_python_parser::ast;
 
-use crate::CodeGen;
+use crate::{get_identifier, CodeGen, STR_BUFFER_SIZE};
 
 impl<'a, 'ctx> CodeGen<'a, 'ctx> {
     pub fn emit_expr(&mut self, expr: &ast::Expression) -> Result<Value<'ctx>, LLVMCompileError> {
@@ -17,15 +23,7 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         use dsp_python_parser::ast::ExpressionType;
         match &expr.node {
             ExpressionType::Number { value } => match value {
-                ast::Number::Integer { value } => {
-                    let value = Value::I16 {
-                        value: self
-                            .context
-                            .i16_type()
-                            .const_int(truncate_bigint_to_u64(value), true),
-                    };
-                    Ok(value)
-                }
+                ast::Number::Integer { value } => self.compile_int_literal(value),
                 ast::Number::Float { value } => {
                     let value = Value::F32 {
                         value: self.context.f32_type().const_float(*value),
@@ -39,7 +37,16 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
                 ),
             },
             ExpressionType::String { value } => {
-                let v = try_get_constant_string(value).unwrap();
+                let v = match try_get_constant_string(value) {
+                    Some(v) => v,
+                    None => {
+                        return err!(
+                            self,
+                            LLVMCompileErrorType::NotImplemented,
+                            "f-strings are only supported as an argument of print and println."
+                        );
+                    }
+                };
                 if self._fn_value.is_some() {
                     let value = Value::Str {
                         value: self
@@ -61,27 +68,54 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
                 args,
                 keywords,
             } => {
+                match &function.node {
+                    ExpressionType::Identifier { name }
+                        if PRINT_FUNCTIONS.contains(&name.as_str()) =>
+                    {
+                        return self.compile_print(args, keywords, name == "println");
+                    }
+                    // `Serial1.println(...)` prints to the port of the object
+                    ExpressionType::Attribute { value, name }
+                        if PRINT_FUNCTIONS.contains(&name.as_str())
+                            && self.get_method(value, name).as_ref() == Some(name) =>
+                    {
+                        return self.compile_print_to(value, args, keywords, name == "println");
+                    }
+                    _ => {}
+                }
                 // TODO: kwargs
                 let _keywords = keywords;
                 self.compile_expr_call(function, args)
             }
             ExpressionType::Identifier { name } => {
-                let (value_type, pointer_value) = if let Some(fn_value) = self._fn_value {
-                    let llvm_variable = self.locals.load(&fn_value, name);
-                    if let Some(llvm_variable) = llvm_variable {
-                        llvm_variable
-                    } else {
-                        let llvm_variable = self.globals.load(name);
-                        if let Some(llvm_variable) = llvm_variable {
-                            llvm_variable
-                        } else {
-                            return err!(self, LLVMCompileErrorType::NameError, name);
-                        }
-                    }
+                let local = match self._fn_value {
+                    Some(fn_value) => self.locals.load(&fn_value, name),
+                    None => None,
+                };
+                let (value_type, pointer_value) = if let Some(llvm_variable) = local {
+                    llvm_variable
                 } else {
-                    let llvm_variable = self.globals.load(name);
+                    let qualified_name = self.resolve(name);
+                    // Globals in the EEPROM are read on every access
+                    if let Some(&(value_type, pointer)) = qualified_name
+                        .as_ref()
+                        .and_then(|qualified_name| self.persistent_globals.load(qualified_name))
+                    {
+                        return self.build_persistent_read(value_type, pointer, name);
+                    }
+                    let llvm_variable = match &qualified_name {
+                        Some(qualified_name) => self.globals.load(qualified_name),
+                        None => None,
+                    };
                     if let Some(llvm_variable) = llvm_variable {
+                        if self.compile_context.interrupt {
+                            self.volatile_globals.insert(qualified_name.unwrap());
+                        }
                         llvm_variable
+                    } else if let Some(f) = qualified_name.and_then(|name| self.get_function(&name))
+                    {
+                        // A reference to a function such as an interrupt handler
+                        return self.compile_function_pointer(name, f);
                     } else {
                         return err!(self, LLVMCompileErrorType::NameError, name);
                     }
@@ -92,59 +126,61 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
                 );
                 Ok(value)
             }
-            ExpressionType::Compare { vals, ops } => self.compile_comparison(vals, ops),
-            ExpressionType::Binop { a, op, b } => self.compile_bin_op(a, op, b),
-            ExpressionType::BoolOp { op, values } => self.compile_bool_op(op, values),
-            ExpressionType::Unop { op, a } => match &a.node {
-                ExpressionType::Number { value } => match value {
-                    ast::Number::Integer { value } => match op {
-                        ast::UnaryOperator::Neg => {
-                            let value = Value::I16 {
-                                value: self
-                                    .context
-                                    .i16_type()
-                                    .const_int(truncate_bigint_to_u64(&-value), true),
-                            };
-                            Ok(value)
-                        }
-                        _ => {
+            ExpressionType::Attribute { value, name } => {
+                // Globals of imported modules
+                let qualified_name = match &value.node {
+                    ExpressionType::Identifier { name: alias } => {
+                        self.resolve_attribute(alias, name)
+                    }
+                    _ => None,
+                };
+                if let Some(&(value_type, pointer)) = qualified_name
+                    .as_ref()
+                    .and_then(|qualified_name| self.persistent_globals.load(qualified_name))
+                {
+                    return self.build_persistent_read(value_type, pointer, name);
+                }
+                let (value_type, pointer_value) =
+                    match qualified_name.and_then(|name| self.globals.load(&name)) {
+                        Some(llvm_variable) => llvm_variable,
+                        None => {
                             return err!(
                                 self,
                                 LLVMCompileErrorType::NotImplemented,
-                                format!("Unimplemented unop {:?} for integer", op)
+                                "Attributes are only supported for the globals of modules."
                             );
                         }
+                    };
+                let value = Value::from_basic_value(
+                    value_type.to_owned(),
+                    self.builder.build_load(*pointer_value, name),
+                );
+                Ok(value)
+            }
+            ExpressionType::Subscript { a, b } => self.compile_subscript(a, b),
+            ExpressionType::Compare { vals, ops } => self.compile_comparison(vals, ops),
+            ExpressionType::Binop { a, op, b } => self.compile_bin_op(a, op, b),
+            ExpressionType::BoolOp { op, values } => self.compile_bool_op(op, values),
+            ExpressionType::Unop { op, a } => match (op, &a.node) {
+                // Negative literals are constants
+                (
+                    ast::UnaryOperator::Neg,
+                    ExpressionType::Number {
+                        value: ast::Number::Integer { value },
                     },
-                    ast::Number::Float { value } => match op {
-                        ast::UnaryOperator::Neg => {
-                            let value = Value::F32 {
-                                value: self.context.f32_type().const_float(-value.clone()),
-                            };
-                            Ok(value)
-                        }
-                        _ => {
-                            return err!(
-                                self,
-                                LLVMCompileErrorType::NotImplemented,
-                                format!("Unimplemented unop {:?} for floating number", op)
-                            );
-                        }
+                ) => self.compile_int_literal(&-value),
+                (
+                    ast::UnaryOperator::Neg,
+                    ExpressionType::Number {
+                        value: ast::Number::Float { value },
                     },
-                    ast::Number::Complex { real: _, imag: _ } => {
-                        return err!(
-                            self,
-                            LLVMCompileErrorType::NotImplemented,
-                            format!("Complex numbers are not implemented.")
-                        );
-                    }
-                },
-                _ => {
-                    return err!(
-                        self,
-                        LLVMCompileErrorType::NotImplemented,
-                        format!("unary operator for {:?} is not implemented.", a.node)
-                    );
+                ) => {
+                    let value = Value::F32 {
+                        value: self.context.f32_type().const_float(-value.clone()),
+                    };
+                    Ok(value)
                 }
+                _ => self.compile_unary_op(op, a),
             },
             ExpressionType::List { elements } | ExpressionType::Tuple { elements } => {
                 let mut elements_value = vec![];
@@ -179,13 +215,184 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         }
     }
 
+    fn compile_unary_op(
+        &mut self,
+        op: &ast::UnaryOperator,
+        a: &ast::Expression,
+    ) -> Result<Value<'ctx>, LLVMCompileError> {
+        let value = self.emit_expr(a)?;
+        let value_type = value.get_type();
+
+        // `not x` is `x == 0`
+        if let ast::UnaryOperator::Not = op {
+            let value = match value.to_basic_value() {
+                BasicValueEnum::IntValue(value) => self.builder.build_int_compare(
+                    IntPredicate::EQ,
+                    value,
+                    value.get_type().const_zero(),
+                    "not",
+                ),
+                BasicValueEnum::FloatValue(value) => self.builder.build_float_compare(
+                    FloatPredicate::OEQ,
+                    value,
+                    value.get_type().const_zero(),
+                    "not",
+                ),
+                _ => {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::NotImplemented,
+                        format!("Unimplemented unop {:?} for {:?}", op, value_type)
+                    );
+                }
+            };
+            return Ok(Value::Bool { value });
+        }
+
+        if value_type.is_integer() {
+            // Booleans and the integers narrower than int are promoted to int like C
+            let value_type = if value_type.get_bitwidth() < 16 {
+                ValueType::I16
+            } else {
+                value_type
+            };
+            let operand = self
+                .build_cast_value(value, value_type.to_basic_type(self.context))?
+                .into_int_value();
+            let result = match op {
+                ast::UnaryOperator::Pos => operand,
+                ast::UnaryOperator::Neg => self.builder.build_int_neg(operand, "neg"),
+                ast::UnaryOperator::Inv => self.builder.build_not(operand, "inv"),
+                ast::UnaryOperator::Not => unreachable!(),
+            };
+            return Ok(Value::from_basic_value(value_type, result.into()));
+        }
+
+        match (op, value) {
+            (ast::UnaryOperator::Pos, Value::F32 { .. }) => Ok(value),
+            (ast::UnaryOperator::Neg, Value::F32 { value }) => Ok(Value::F32 {
+                value: self.builder.build_float_neg(value, "fneg"),
+            }),
+            _ => err!(
+                self,
+                LLVMCompileErrorType::NotImplemented,
+                format!("Unimplemented unop {:?} for {:?}", op, value_type)
+            ),
+        }
+    }
+
+    /// Get the class of the object which an expression evaluates to
+    pub fn get_expr_class(&self, expr: &ast::Expression) -> Option<String> {
+        match &expr.node {
+            ast::ExpressionType::Identifier { name } => {
+                if let Some(fn_value) = &self._fn_value {
+                    if self.locals.load(fn_value, name).is_some() {
+                        return self.compile_context.local_classes.get(name).cloned();
+                    }
+                }
+                self.object_classes.get(&self.resolve(name)?).cloned()
+            }
+            ast::ExpressionType::Attribute { value, name } => match &value.node {
+                ast::ExpressionType::Identifier { name: alias } => self
+                    .object_classes
+                    .get(&self.resolve_attribute(alias, name)?)
+                    .cloned(),
+                _ => None,
+            },
+            // Functions which return objects such as `SoftwareSerial(10, 11)`
+            ast::ExpressionType::Call { function, .. } => {
+                let symbol = match &function.node {
+                    ast::ExpressionType::Identifier { name } => self.resolve(name)?,
+                    ast::ExpressionType::Attribute { value, name } => match &value.node {
+                        ast::ExpressionType::Identifier { name: alias } => {
+                            self.resolve_attribute(alias, name)?
+                        }
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+                self.return_classes.get(&symbol).cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Check if an expression evaluates to the buffer of a concatenation,
+    /// which is only valid until the current function returns
+    pub fn is_stack_str(&self, expr: &ast::Expression, value: &Value<'ctx>) -> bool {
+        let pointer = match value {
+            Value::Str { value } => value,
+            _ => return false,
+        };
+        match &expr.node {
+            ast::ExpressionType::Identifier { name } => {
+                !self.compile_context.global_names.contains(name)
+                    && self.compile_context.stack_strs.contains(name)
+            }
+            _ => self.str_buffers.contains(pointer),
+        }
+    }
+
+    /// Get the symbol of a method of the object which an expression evaluates to
+    fn get_method(&self, object: &ast::Expression, name: &str) -> Option<String> {
+        let class = self.get_expr_class(object)?;
+        self.classes[&class].get_method(name).cloned()
+    }
+
+    fn compile_function_pointer(
+        &self,
+        name: &str,
+        f: FunctionValue<'ctx>,
+    ) -> Result<Value<'ctx>, LLVMCompileError> {
+        let pointer_type = get_function_pointer_type(self.context);
+        if f.get_type().ptr_type(AddressSpace::Generic) != pointer_type {
+            return err!(
+                self,
+                LLVMCompileErrorType::TypeError,
+                "a function without arguments returning None",
+                name
+            );
+        }
+        Ok(Value::Function {
+            value: f.as_global_value().as_pointer_value(),
+        })
+    }
+
     fn compile_expr_call(
         &mut self,
         func: &Box<ast::Expression>,
         args: &Vec<ast::Expression>,
     ) -> Result<Value<'ctx>, LLVMCompileError> {
+        // The handle of the object whose method is called
+        let mut receiver = None;
         let func_name = match &func.node {
-            ast::ExpressionType::Identifier { name } => name.to_string(),
+            ast::ExpressionType::Identifier { name } => match self.resolve(name) {
+                Some(qualified_name) => qualified_name,
+                None => return err!(self, LLVMCompileErrorType::NameError, name),
+            },
+            ast::ExpressionType::Attribute { value, name } => {
+                // Functions of imported modules
+                let qualified_name = match &value.node {
+                    ast::ExpressionType::Identifier { name: alias } => {
+                        self.resolve_attribute(alias, name)
+                    }
+                    _ => None,
+                };
+                match (qualified_name, self.get_method(value, name)) {
+                    (Some(qualified_name), _) => qualified_name,
+                    (None, Some(symbol)) => {
+                        receiver = Some(self.emit_expr(value)?);
+                        symbol
+                    }
+                    (None, None) => {
+                        return err!(
+                            self,
+                            LLVMCompileErrorType::NotImplemented,
+                            "Calling method is not implemented."
+                        );
+                    }
+                }
+            }
             _ => {
                 return err!(
                     self,
@@ -195,73 +402,111 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
             }
         };
 
-        // Compile the first argument to get type signature
-        let first_arg = if let Some(f_arg) = args.first() {
-            self.emit_expr(f_arg)?
-        } else {
-            Value::Void
-        };
+        let mut values = receiver.into_iter().collect::<Vec<Value>>();
+        for arg in args.iter() {
+            values.push(self.emit_expr(arg)?);
+        }
+        let arg_types = values
+            .iter()
+            .map(|value| value.get_type())
+            .collect::<Vec<ValueType>>();
 
         let func = match self.get_function(&func_name) {
             Some(f) => f,
             None => {
-                // Simple mangling from the type of the first argument
-                let func_name_mangled = get_mangled_name(&func_name, first_arg.get_type());
-                if let Some(f) = self.get_function(&func_name_mangled) {
-                    f
-                } else {
+                // Find the overload which fits the types of the arguments
+                let overloads = self.get_overloads(&func_name);
+                if overloads.is_empty() {
                     return err!(self, LLVMCompileErrorType::NameError, &func_name);
                 }
+                let constants = values
+                    .iter()
+                    .map(
+                        |value| match (value.get_type().get_group(), value.to_basic_value()) {
+                            (ValueTypeGroup::Int, BasicValueEnum::IntValue(value)) => {
+                                value.get_sign_extended_constant()
+                            }
+                            (ValueTypeGroup::UInt, BasicValueEnum::IntValue(value)) => value
+                                .get_zero_extended_constant()
+                                .map(|constant| constant as i64),
+                            _ => None,
+                        },
+                    )
+                    .collect::<Vec<Option<i64>>>();
+                match resolve_overload(&overloads, &arg_types, &constants) {
+                    Ok(mangled_name) => self.get_function(mangled_name).unwrap(),
+                    Err(OverloadError::NoMatch) => {
+                        return err!(
+                            self,
+                            LLVMCompileErrorType::TypeError,
+                            format!(
+                                "one of {}",
+                                overloads
+                                    .iter()
+                                    .map(|(name, _)| name.as_str())
+                                    .collect::<Vec<&str>>()
+                                    .join(", ")
+                            ),
+                            format!("{}({:?})", func_name, arg_types)
+                        );
+                    }
+                    Err(OverloadError::Ambiguous(candidates)) => {
+                        return err!(
+                            self,
+                            LLVMCompileErrorType::TypeError,
+                            format!("a single best match among {}", candidates.join(", ")),
+                            format!("{}({:?})", func_name, arg_types)
+                        );
+                    }
+                }
             }
         };
 
-        let args_proto = func.get_params();
-        let mut args_value: Vec<BasicValueEnum> = vec![];
-
-        for (i, (expr, proto)) in args.iter().zip(args_proto.iter()).enumerate() {
-            // Prevent compile the same argument twice
-            let value = if i == 0 {
-                first_arg
-            } else {
-                self.emit_expr(expr)?
-            };
+        let args_proto = func.get_type().get_param_types();
+        if args_proto.len() != values.len() {
+            return err!(
+                self,
+                LLVMCompileErrorType::TypeError,
+                format!("{} arguments", args_proto.len()),
+                format!("{} arguments", values.len())
+            );
+        }
 
-            // Convert the type of argument according to the signature
-            match value {
-                Value::Bool { value } => {
-                    let cast = self.builder.build_int_truncate(
-                        value,
-                        proto.get_type().into_int_type(),
-                        "itrunc",
-                    );
-                    args_value.push(BasicValueEnum::IntValue(cast))
-                }
-                Value::I8 { value } => {
-                    let cast = self.builder.build_int_cast(
-                        value,
-                        proto.get_type().into_int_type(),
-                        "icast",
-                    );
-                    args_value.push(BasicValueEnum::IntValue(cast))
-                }
-                Value::I16 { value } => {
-                    let cast = self.builder.build_int_truncate(
-                        value,
-                        proto.get_type().into_int_type(),
-                        "itrunc",
-                    );
-                    args_value.push(BasicValueEnum::IntValue(cast))
-                }
-                Value::F32 { value } => args_value.push(BasicValueEnum::FloatValue(value)),
-                Value::Str { value } => args_value.push(BasicValueEnum::PointerValue(value)),
-                _ => {
-                    return err!(
-                        self,
-                        LLVMCompileErrorType::NotImplemented,
-                        format!("Unimplemented argument type '{:?}'", value.get_type())
-                    );
-                }
+        // A concatenated str passed to a function must not be stored by it,
+        // and the result may be the same buffer
+        let offset = values.len() - args.len();
+        let mut takes_stack_str = false;
+        for (i, arg) in args.iter().enumerate() {
+            let stack_str = self.is_stack_str(arg, &values[offset + i]);
+            let escaping = matches!(self.escaping_str_params.get(&func),
+                Some(params) if params.contains(&(offset + i)));
+            if escaping && stack_str {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::NotImplemented,
+                    format!(
+                        "The concatenated str can not be passed to {} because it stores the argument to a global",
+                        func_name
+                    )
+                );
+            }
+            // A str parameter passed on to be stored escapes as well
+            let borrowed_str = get_identifier(arg)
+                .and_then(|name| self.compile_context.borrowed_strs.get(name))
+                .copied();
+            if let (true, Some(index), Some(fn_value)) = (escaping, borrowed_str, self._fn_value) {
+                self.escaping_str_params
+                    .entry(fn_value)
+                    .or_default()
+                    .insert(index);
             }
+            takes_stack_str |= stack_str;
+        }
+
+        // Convert the type of arguments according to the signature
+        let mut args_value: Vec<BasicValueEnum> = vec![];
+        for (value, proto) in values.into_iter().zip(args_proto.into_iter()) {
+            args_value.push(self.build_cast_value(value, proto)?);
         }
 
         let res = self.builder.build_call(func, args_value.as_slice(), "call");
@@ -271,16 +516,30 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         let value = match res.try_as_basic_value() {
             // Return type
             Either::Left(bv) => {
-                let vt = if bv.is_int_value() {
+                let return_type = self
+                    .return_types
+                    .get(func.get_name().to_str().unwrap_or_default())
+                    .cloned();
+                let vt = if let Some(return_type) = return_type {
+                    return_type
+                } else if bv.is_int_value() {
                     let iv = bv.into_int_value();
 
                     match iv.get_type().get_bit_width() {
+                        1 => ValueType::Bool,
                         8 => ValueType::I8,
                         16 => ValueType::I16,
+                        32 => ValueType::I32,
                         _ => unreachable!(),
                     }
                 } else if bv.is_float_value() {
-                    ValueType::F32
+                    if bv.into_float_value().get_type() == self.context.f64_type() {
+                        ValueType::F64
+                    } else {
+                        ValueType::F32
+                    }
+                } else if bv.is_pointer_value() {
+                    ValueType::Str
                 } else {
                     unreachable!()
                 };
@@ -288,6 +547,9 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
             }
             Either::Right(_) => Value::Void,
         };
+        if let (true, Value::Str { value }) = (takes_stack_str, &value) {
+            self.str_buffers.insert(*value);
+        }
         Ok(value)
     }
 
@@ -307,57 +569,41 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         let a = self.emit_expr(vals.first().unwrap())?;
         let b = self.emit_expr(vals.last().unwrap())?;
 
-        Ok(a.invoke_handler(
-            ValueHandler::new()
-                .handle_int(&|_, lhs_value| {
-                    b.invoke_handler(ValueHandler::new().handle_int(&|_, rhs_value| {
-                        let int_predicate = match ops.first().unwrap() {
-                            ast::Comparison::Equal => IntPredicate::EQ,
-                            ast::Comparison::NotEqual => IntPredicate::NE,
-                            ast::Comparison::Greater => IntPredicate::SGT,
-                            ast::Comparison::Less => IntPredicate::SLT,
-                            ast::Comparison::GreaterOrEqual => IntPredicate::SGE,
-                            ast::Comparison::LessOrEqual => IntPredicate::SLE,
-                            _ => panic!(
-                                "Unsupported {:?} comparison operator for integer",
-                                ops.first().unwrap()
-                            ),
-                        };
-                        Value::Bool {
-                            value: self.builder.build_int_compare(
-                                int_predicate,
-                                lhs_value,
-                                rhs_value,
-                                "a",
-                            ),
-                        }
-                    }))
-                })
-                .handle_float(&|_, lhs_value| {
-                    b.invoke_handler(ValueHandler::new().handle_float(&|_, rhs_value| {
-                        let float_predicate = match ops.first().unwrap() {
-                            ast::Comparison::Equal => FloatPredicate::OEQ,
-                            ast::Comparison::NotEqual => FloatPredicate::ONE,
-                            ast::Comparison::Greater => FloatPredicate::OGT,
-                            ast::Comparison::Less => FloatPredicate::OLT,
-                            ast::Comparison::GreaterOrEqual => FloatPredicate::OGE,
-                            ast::Comparison::LessOrEqual => FloatPredicate::OLE,
-                            _ => panic!(
-                                "Unsupported {:?} comparison operator for floating number",
-                                ops.first().unwrap()
-                            ),
-                        };
-                        Value::Bool {
-                            value: self.builder.build_float_compare(
-                                float_predicate,
-                                lhs_value,
-                                rhs_value,
-                                "a",
-                            ),
-                        }
-                    }))
-                }),
-        ))
+        if a.get_type() == ValueType::Str || b.get_type() == ValueType::Str {
+            return self.compile_str_comparison(a, ops.first().unwrap(), b);
+        }
+        if a.get_type().is_integer() && b.get_type().is_integer() {
+            return self.compile_int_comparison(a, ops.first().unwrap(), b);
+        }
+        // Integers are converted to float before the comparisons with floats
+        let (a, b) = (self.build_float_operand(a)?, self.build_float_operand(b)?);
+
+        Ok(
+            a.invoke_handler(ValueHandler::new().handle_float(&|_, lhs_value| {
+                b.invoke_handler(ValueHandler::new().handle_float(&|_, rhs_value| {
+                    let float_predicate = match ops.first().unwrap() {
+                        ast::Comparison::Equal => FloatPredicate::OEQ,
+                        ast::Comparison::NotEqual => FloatPredicate::ONE,
+                        ast::Comparison::Greater => FloatPredicate::OGT,
+                        ast::Comparison::Less => FloatPredicate::OLT,
+                        ast::Comparison::GreaterOrEqual => FloatPredicate::OGE,
+                        ast::Comparison::LessOrEqual => FloatPredicate::OLE,
+                        _ => panic!(
+                            "Unsupported {:?} comparison operator for floating number",
+                            ops.first().unwrap()
+                        ),
+                    };
+                    Value::Bool {
+                        value: self.builder.build_float_compare(
+                            float_predicate,
+                            lhs_value,
+                            rhs_value,
+                            "a",
+                        ),
+                    }
+                }))
+            })),
+        )
     }
 
     fn compile_bin_op(
@@ -369,160 +615,426 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         use dsp_python_parser::ast::Operator;
         let a = self.emit_expr(a)?;
         let b = self.emit_expr(b)?;
-        Ok(a.invoke_handler(
-            ValueHandler::new()
-                .handle_int(&|_, lhs_value| {
-                    macro_rules! emit_cast {
-                        () => {{
-                            self.builder
-                                .build_cast(
-                                    InstructionOpcode::SIToFP,
-                                    lhs_value,
-                                    self.context.f32_type(),
-                                    "sitofp",
-                                )
-                                .into_float_value()
-                        }};
+
+        if a.get_type() == ValueType::Str || b.get_type() == ValueType::Str {
+            return self.compile_str_bin_op(a, op, b);
+        }
+        if a.get_type().is_integer() && b.get_type().is_integer() {
+            return self.compile_int_bin_op(a, op, b);
+        }
+        // Integers are converted to float before the operations with floats
+        let (a, b) = (self.build_float_operand(a)?, self.build_float_operand(b)?);
+
+        Ok(
+            a.invoke_handler(ValueHandler::new().handle_float(&|_, lhs_value| {
+                b.invoke_handler(ValueHandler::new().handle_float(&|_, rhs_value| {
+                    let value = match op {
+                        Operator::Add => self.builder.build_float_add(lhs_value, rhs_value, "add"),
+                        Operator::Sub => self.builder.build_float_sub(lhs_value, rhs_value, "sub"),
+                        Operator::Mult => self.builder.build_float_mul(lhs_value, rhs_value, "mul"),
+                        Operator::Div => self.builder.build_float_div(lhs_value, rhs_value, "div"),
+                        Operator::FloorDiv => unimplemented!(),
+                        Operator::Mod => self.builder.build_float_rem(lhs_value, rhs_value, "mod"),
+                        _ => panic!("Unimplemented {:?} operator for f32", op),
                     };
-                    b.invoke_handler(
-                        ValueHandler::new()
-                            // Between int and int
-                            .handle_int(&|_, rhs_value| {
-                                // Div operator to int returns a float.
-                                if op == &Operator::Div {
-                                    return Value::F32 {
-                                        value: self.builder.build_float_div(
-                                            lhs_value
-                                                .const_signed_to_float(self.context.f32_type()),
-                                            rhs_value
-                                                .const_signed_to_float(self.context.f32_type()),
-                                            "div",
-                                        ),
-                                    };
-                                }
-                                let value = match op {
-                                    Operator::Add => {
-                                        self.builder.build_int_add(lhs_value, rhs_value, "add")
-                                    }
-                                    Operator::Sub => {
-                                        self.builder.build_int_sub(lhs_value, rhs_value, "sub")
-                                    }
-                                    Operator::Mult => {
-                                        self.builder.build_int_mul(lhs_value, rhs_value, "mul")
-                                    }
-                                    Operator::Div => {
-                                        // In Python, dividing int by int returns a float,
-                                        // which is implemented above.
-                                        unimplemented!()
-                                    }
-                                    Operator::FloorDiv => self
-                                        .builder
-                                        .build_int_signed_div(lhs_value, rhs_value, "fld"),
-                                    Operator::Mod => self
-                                        .builder
-                                        .build_int_signed_rem(lhs_value, rhs_value, "mod"),
-                                    _ => panic!("Unimplemented {:?} operator for i16", op),
-                                };
-                                Value::I16 { value }
-                            })
-                            // Between int and float
-                            .handle_float(&|_, rhs_value| {
-                                let cast = emit_cast!();
-                                let value = match op {
-                                    Operator::Add => {
-                                        self.builder.build_float_add(cast, rhs_value, "add")
-                                    }
-                                    Operator::Sub => {
-                                        self.builder.build_float_sub(cast, rhs_value, "sub")
-                                    }
-                                    Operator::Mult => {
-                                        self.builder.build_float_mul(cast, rhs_value, "mul")
-                                    }
-                                    Operator::Div => {
-                                        self.builder.build_float_div(cast, rhs_value, "div")
-                                    }
-                                    Operator::FloorDiv => unimplemented!(),
-                                    Operator::Mod => {
-                                        self.builder.build_float_rem(cast, rhs_value, "mod")
-                                    }
-                                    _ => panic!("Unimplemented {:?} operator for i16 and f32", op),
-                                };
-                                Value::F32 { value }
-                            }),
-                    )
+                    Value::F32 { value }
+                }))
+            })),
+        )
+    }
+
+    fn compile_int_literal(&self, value: &BigInt) -> Result<Value<'ctx>, LLVMCompileError> {
+        // Integer literals are `int` unless they need a wider type
+        let value = match value.to_i64() {
+            Some(v) if v >= i16::MIN as i64 && v <= i16::MAX as i64 => Value::I16 {
+                value: self.context.i16_type().const_int(v as u64, true),
+            },
+            Some(v) if v >= i32::MIN as i64 && v <= i32::MAX as i64 => Value::I32 {
+                value: self.context.i32_type().const_int(v as u64, true),
+            },
+            Some(v) if v >= 0 && v <= u32::MAX as i64 => Value::U32 {
+                value: self.context.i32_type().const_int(v as u64, false),
+            },
+            _ => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::NotImplemented,
+                    format!("The integer {} is too large.", value)
+                );
+            }
+        };
+        Ok(value)
+    }
+
+    /// Compile an operation between integers after converting them to their common type
+    fn compile_int_bin_op(
+        &mut self,
+        a: Value<'ctx>,
+        op: &ast::Operator,
+        b: Value<'ctx>,
+    ) -> Result<Value<'ctx>, LLVMCompileError> {
+        use dsp_python_parser::ast::Operator;
+
+        // In Python, dividing int by int returns a float
+        if op == &Operator::Div {
+            let f32_type = self.context.f32_type().into();
+            let lhs_value = self.build_cast_value(a, f32_type)?.into_float_value();
+            let rhs_value = self.build_cast_value(b, f32_type)?.into_float_value();
+            return Ok(Value::F32 {
+                value: self.builder.build_float_div(lhs_value, rhs_value, "div"),
+            });
+        }
+
+        let common_type = get_common_int_type(a.get_type(), b.get_type());
+        let llvm_type = common_type.to_basic_type(self.context);
+        let lhs_value = self.build_cast_value(a, llvm_type)?.into_int_value();
+        let rhs_value = self.build_cast_value(b, llvm_type)?.into_int_value();
+        // Unsigned integers wrap around like C
+        let unsigned = common_type.get_group() == ValueTypeGroup::UInt;
+
+        let value = match op {
+            Operator::Add => self.builder.build_int_add(lhs_value, rhs_value, "add"),
+            Operator::Sub => self.builder.build_int_sub(lhs_value, rhs_value, "sub"),
+            Operator::Mult => self.builder.build_int_mul(lhs_value, rhs_value, "mul"),
+            Operator::FloorDiv if unsigned => self
+                .builder
+                .build_int_unsigned_div(lhs_value, rhs_value, "fld"),
+            Operator::FloorDiv => self
+                .builder
+                .build_int_signed_div(lhs_value, rhs_value, "fld"),
+            Operator::Mod if unsigned => self
+                .builder
+                .build_int_unsigned_rem(lhs_value, rhs_value, "mod"),
+            Operator::Mod => self
+                .builder
+                .build_int_signed_rem(lhs_value, rhs_value, "mod"),
+            Operator::BitAnd => self.builder.build_and(lhs_value, rhs_value, "and"),
+            Operator::BitOr => self.builder.build_or(lhs_value, rhs_value, "or"),
+            Operator::BitXor => self.builder.build_xor(lhs_value, rhs_value, "xor"),
+            Operator::LShift => self.builder.build_left_shift(lhs_value, rhs_value, "shl"),
+            Operator::RShift => self
+                .builder
+                .build_right_shift(lhs_value, rhs_value, !unsigned, "shr"),
+            _ => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::NotImplemented,
+                    format!("Unimplemented {:?} operator for {:?}", op, common_type)
+                );
+            }
+        };
+        Ok(Value::from_basic_value(common_type, value.into()))
+    }
+
+    fn compile_int_comparison(
+        &mut self,
+        a: Value<'ctx>,
+        op: &ast::Comparison,
+        b: Value<'ctx>,
+    ) -> Result<Value<'ctx>, LLVMCompileError> {
+        let common_type = get_common_int_type(a.get_type(), b.get_type());
+        let llvm_type = common_type.to_basic_type(self.context);
+        let lhs_value = self.build_cast_value(a, llvm_type)?.into_int_value();
+        let rhs_value = self.build_cast_value(b, llvm_type)?.into_int_value();
+        let unsigned = common_type.get_group() == ValueTypeGroup::UInt;
+
+        let int_predicate = match op {
+            ast::Comparison::Equal => IntPredicate::EQ,
+            ast::Comparison::NotEqual => IntPredicate::NE,
+            ast::Comparison::Greater if unsigned => IntPredicate::UGT,
+            ast::Comparison::Less if unsigned => IntPredicate::ULT,
+            ast::Comparison::GreaterOrEqual if unsigned => IntPredicate::UGE,
+            ast::Comparison::LessOrEqual if unsigned => IntPredicate::ULE,
+            ast::Comparison::Greater => IntPredicate::SGT,
+            ast::Comparison::Less => IntPredicate::SLT,
+            ast::Comparison::GreaterOrEqual => IntPredicate::SGE,
+            ast::Comparison::LessOrEqual => IntPredicate::SLE,
+            _ => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::NotImplemented,
+                    format!("Unsupported {:?} comparison operator for integer", op)
+                );
+            }
+        };
+        Ok(Value::Bool {
+            value: self
+                .builder
+                .build_int_compare(int_predicate, lhs_value, rhs_value, "a"),
+        })
+    }
+
+    /// Convert integers to float for the operations with floats
+    fn build_float_operand(&self, value: Value<'ctx>) -> Result<Value<'ctx>, LLVMCompileError> {
+        match value.get_type().get_group() {
+            ValueTypeGroup::Bool | ValueTypeGroup::Int | ValueTypeGroup::UInt => {
+                let cast = self.build_cast_value(value, self.context.f32_type().into())?;
+                Ok(Value::F32 {
+                    value: cast.into_float_value(),
                 })
-                .handle_float(&|_, lhs_value| {
-                    b.invoke_handler(
-                        ValueHandler::new()
-                            // Between float and float
-                            .handle_float(&|_, rhs_value| {
-                                let value = match op {
-                                    Operator::Add => {
-                                        self.builder.build_float_add(lhs_value, rhs_value, "add")
-                                    }
-                                    Operator::Sub => {
-                                        self.builder.build_float_sub(lhs_value, rhs_value, "sub")
-                                    }
-                                    Operator::Mult => {
-                                        self.builder.build_float_mul(lhs_value, rhs_value, "mul")
-                                    }
-                                    Operator::Div => {
-                                        self.builder.build_float_div(lhs_value, rhs_value, "div")
-                                    }
-                                    Operator::FloorDiv => unimplemented!(),
-                                    Operator::Mod => {
-                                        self.builder.build_float_rem(lhs_value, rhs_value, "mod")
-                                    }
-                                    _ => panic!("Unimplemented {:?} operator for f32", op),
-                                };
-                                Value::F32 { value }
-                            })
-                            // Between float and int
-                            .handle_int(&|_, rhs_value| {
-                                macro_rules! emit_cast {
-                                    () => {{
-                                        self.builder
-                                            .build_cast(
-                                                InstructionOpcode::SIToFP,
-                                                rhs_value,
-                                                self.context.f32_type(),
-                                                "sitofp",
-                                            )
-                                            .into_float_value()
-                                    }};
-                                };
-                                let cast = emit_cast!();
-                                let value = match op {
-                                    Operator::Add => {
-                                        self.builder.build_float_add(lhs_value, cast, "add")
-                                    }
-                                    Operator::Sub => {
-                                        self.builder.build_float_sub(lhs_value, cast, "sub")
-                                    }
-                                    Operator::Mult => {
-                                        self.builder.build_float_mul(lhs_value, cast, "mul")
-                                    }
-                                    Operator::Div => {
-                                        self.builder.build_float_div(lhs_value, cast, "div")
-                                    }
-                                    Operator::FloorDiv => unimplemented!(),
-                                    Operator::Mod => {
-                                        self.builder.build_float_rem(lhs_value, cast, "mod")
-                                    }
-                                    _ => panic!("Unimplemented {:?} operator for f32 and i16", op),
-                                };
-                                Value::F32 { value }
-                            }),
-                    )
-                }),
-        ))
+            }
+            _ => Ok(value),
+        }
     }
 
+    fn compile_str_comparison(
+        &mut self,
+        a: Value<'ctx>,
+        op: &ast::Comparison,
+        b: Value<'ctx>,
+    ) -> Result<Value<'ctx>, LLVMCompileError> {
+        let (lhs_value, rhs_value) = match (a, b) {
+            (Value::Str { value: lhs_value }, Value::Str { value: rhs_value }) => {
+                (lhs_value, rhs_value)
+            }
+            (Value::Str { .. }, _) => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::TypeError,
+                    "str",
+                    format!("{:?}", b.get_type())
+                );
+            }
+            _ => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::TypeError,
+                    "str",
+                    format!("{:?}", a.get_type())
+                );
+            }
+        };
+        let int_predicate = match op {
+            ast::Comparison::Equal => IntPredicate::EQ,
+            ast::Comparison::NotEqual => IntPredicate::NE,
+            ast::Comparison::Greater => IntPredicate::SGT,
+            ast::Comparison::Less => IntPredicate::SLT,
+            ast::Comparison::GreaterOrEqual => IntPredicate::SGE,
+            ast::Comparison::LessOrEqual => IntPredicate::SLE,
+            _ => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::NotImplemented,
+                    format!("Unsupported {:?} comparison operator for str", op)
+                );
+            }
+        };
+
+        // `str_compare` works like `strcmp` and returns a negative, zero or positive integer
+        let str_compare = self.get_function("str_compare").unwrap();
+        let res = self.builder.build_call(
+            str_compare,
+            &[
+                BasicValueEnum::PointerValue(lhs_value),
+                BasicValueEnum::PointerValue(rhs_value),
+            ],
+            "strcmp",
+        );
+        let ordering = res.try_as_basic_value().left().unwrap().into_int_value();
+
+        Ok(Value::Bool {
+            value: self.builder.build_int_compare(
+                int_predicate,
+                ordering,
+                self.context.i16_type().const_zero(),
+                "a",
+            ),
+        })
+    }
+
+    fn compile_str_bin_op(
+        &mut self,
+        a: Value<'ctx>,
+        op: &ast::Operator,
+        b: Value<'ctx>,
+    ) -> Result<Value<'ctx>, LLVMCompileError> {
+        if op != &ast::Operator::Add {
+            return err!(
+                self,
+                LLVMCompileErrorType::NotImplemented,
+                format!("Unimplemented {:?} operator for str", op)
+            );
+        }
+        let (lhs_value, rhs_value) = match (a, b) {
+            (Value::Str { value: lhs_value }, Value::Str { value: rhs_value }) => {
+                (lhs_value, rhs_value)
+            }
+            (Value::Str { .. }, _) => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::TypeError,
+                    "str",
+                    format!("{:?}", b.get_type())
+                );
+            }
+            _ => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::TypeError,
+                    "str",
+                    format!("{:?}", a.get_type())
+                );
+            }
+        };
+
+        // Every concatenation owns a fixed-size buffer on the stack of the current function.
+        // The result is truncated if it does not fit in the buffer.
+        let buffer =
+            self.build_entry_alloca(self.context.i8_type().array_type(STR_BUFFER_SIZE), "strbuf")?;
+        let buffer = self.builder.build_pointer_cast(
+            buffer,
+            self.context.i8_type().ptr_type(AddressSpace::Generic),
+            "strbuf",
+        );
+
+        self.str_buffers.insert(buffer);
+
+        let str_concat = self.get_function("str_concat").unwrap();
+        self.builder.build_call(
+            str_concat,
+            &[
+                BasicValueEnum::PointerValue(buffer),
+                BasicValueEnum::PointerValue(lhs_value),
+                BasicValueEnum::PointerValue(rhs_value),
+                BasicValueEnum::IntValue(
+                    self.context
+                        .i16_type()
+                        .const_int(STR_BUFFER_SIZE as u64, false),
+                ),
+            ],
+            "concat",
+        );
+
+        Ok(Value::Str { value: buffer })
+    }
+
+    fn compile_subscript(
+        &mut self,
+        a: &ast::Expression,
+        b: &ast::Expression,
+    ) -> Result<Value<'ctx>, LLVMCompileError> {
+        let target = self.emit_expr(a)?;
+        let index = self.emit_expr(b)?;
+
+        // The index is converted to `int` by its signedness
+        if !index.get_type().is_integer() {
+            return err!(
+                self,
+                LLVMCompileErrorType::TypeError,
+                "int",
+                format!("{:?}", index.get_type())
+            );
+        }
+        let index = self
+            .build_cast_value(index, self.context.i16_type().into())?
+            .into_int_value();
+
+        match target {
+            // Indexing a string yields the character code
+            Value::Str { value } => {
+                let str_at = self.get_function("str_at").unwrap();
+                let res = self.builder.build_call(
+                    str_at,
+                    &[
+                        BasicValueEnum::PointerValue(value),
+                        BasicValueEnum::IntValue(index),
+                    ],
+                    "index",
+                );
+                Ok(Value::I16 {
+                    value: res.try_as_basic_value().left().unwrap().into_int_value(),
+                })
+            }
+            _ => err!(
+                self,
+                LLVMCompileErrorType::NotImplemented,
+                format!("Subscript of {:?} is not implemented.", target.get_type())
+            ),
+        }
+    }
+
+    /// `and` and `or` evaluate the operands from left to right until the result is decided.
+    /// The result is a bool unlike Python, which gives the last evaluated operand.
     fn compile_bool_op(
         &mut self,
-        _op: &ast::BooleanOperator,
-        _values: &Vec<ast::Expression>,
+        op: &ast::BooleanOperator,
+        values: &Vec<ast::Expression>,
     ) -> Result<Value<'ctx>, LLVMCompileError> {
-        unimplemented!()
+        let parent = self.get_fn_value()?;
+        let end_bb = self.context.append_basic_block(parent, "bool.end");
+
+        // The operand which decided the result and the block where it was evaluated
+        let mut incoming = vec![];
+        for (i, expr) in values.iter().enumerate() {
+            let value = self.emit_expr(expr)?;
+            // The operand is true unless it is 0
+            let cond = match value.to_basic_value() {
+                BasicValueEnum::IntValue(value) => self.builder.build_int_compare(
+                    IntPredicate::NE,
+                    value,
+                    value.get_type().const_zero(),
+                    "bool",
+                ),
+                BasicValueEnum::FloatValue(value) => self.builder.build_float_compare(
+                    FloatPredicate::ONE,
+                    value,
+                    value.get_type().const_zero(),
+                    "bool",
+                ),
+                _ => {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::NotImplemented,
+                        format!("Unimplemented {:?} for {:?}", op, value.get_type())
+                    );
+                }
+            };
+            incoming.push((cond, self.builder.get_insert_block().unwrap()));
+
+            if i == values.len() - 1 {
+                self.builder.build_unconditional_branch(end_bb);
+            } else {
+                // `and` stops at a false operand, and `or` stops at a true one
+                let next_bb = self.context.prepend_basic_block(end_bb, "bool.next");
+                match op {
+                    ast::BooleanOperator::And => {
+                        self.builder.build_conditional_branch(cond, next_bb, end_bb)
+                    }
+                    ast::BooleanOperator::Or => {
+                        self.builder.build_conditional_branch(cond, end_bb, next_bb)
+                    }
+                };
+                self.builder.position_at_end(next_bb);
+            }
+        }
+
+        self.builder.position_at_end(end_bb);
+        let phi = self.builder.build_phi(self.context.bool_type(), "bool");
+        for (cond, bb) in incoming.iter() {
+            phi.add_incoming(&[(cond as &dyn BasicValue, *bb)]);
+        }
+        Ok(Value::Bool {
+            value: phi.as_basic_value().into_int_value(),
+        })
+    }
+}
+
+/// The type of an operation between two integers like the usual arithmetic conversions of C.
+/// The wider type is chosen, and unsigned one is chosen between the types of the same width.
+fn get_common_int_type(a: ValueType, b: ValueType) -> ValueType {
+    // Narrower integers are promoted to `int`
+    let promote = |value_type: ValueType| {
+        if value_type.get_bitwidth() < 16 {
+            ValueType::I16
+        } else {
+            value_type
+        }
+    };
+    let (a, b) = (promote(a), promote(b));
+    match a.get_bitwidth().cmp(&b.get_bitwidth()) {
+        Ordering::Greater => a,
+        Ordering::Less => b,
+        Ordering::Equal if a.get_group() == ValueTypeGroup::UInt => a,
+        Ordering::Equal => b,
     }
 }
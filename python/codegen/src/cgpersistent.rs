@@ -0,0 +1,150 @@
+use inkwell::module::Linkage;
+use inkwell::values::PointerValue;
+use inkwell::AddressSpace;
+
+use dsp_compiler_error::{err, LLVMCompileError, LLVMCompileErrorType};
+use dsp_compiler_value::value::{Value, ValueType, ValueTypeGroup};
+use dsp_python_parser::ast;
+
+use crate::CodeGen;
+
+/// The annotation of the globals kept in the EEPROM, e.g. `Persistent[int]`
+pub const PERSISTENT_ANNOTATION: &str = "Persistent";
+
+/// The section which avr-objcopy extracts into the .eep file
+const EEPROM_SECTION: &str = ".eeprom";
+
+/// The wrappers which copy a value between SRAM and the EEPROM
+const PERSISTENT_READ_FUNCTION: &str = "persistent_read";
+const PERSISTENT_WRITE_FUNCTION: &str = "persistent_write";
+
+impl<'a, 'ctx> CodeGen<'a, 'ctx> {
+    /// Define `threshold: Persistent[int] = 512` in the EEPROM like `EEMEM` of avr-libc.
+    /// The linker assigns the address, and the initial value is written
+    /// to the EEPROM only when the .eep file is uploaded.
+    pub fn compile_stmt_persistent(
+        &mut self,
+        target: &ast::Expression,
+        annotation: &ast::Expression,
+        value: &ast::Expression,
+    ) -> Result<(), LLVMCompileError> {
+        if self._fn_value.is_some() {
+            return err!(
+                self,
+                LLVMCompileErrorType::SyntaxError,
+                "Persistent variables must be defined at module level"
+            );
+        }
+        let name = match &target.node {
+            ast::ExpressionType::Identifier { name } => name,
+            _ => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::NotImplemented,
+                    "Failed to get assignee."
+                );
+            }
+        };
+        let value_type = self.get_annotation_type(annotation)?;
+        if !value_type.is_integer() && value_type.get_group() != ValueTypeGroup::Float {
+            return err!(
+                self,
+                LLVMCompileErrorType::TypeError,
+                "Persistent[int] or Persistent[float]",
+                format!("Persistent[{:?}]", value_type)
+            );
+        }
+
+        // The initial value is written to the .eeprom section, so it must be a literal
+        if !is_literal(value) {
+            return err!(
+                self,
+                LLVMCompileErrorType::TypeError,
+                "a literal such as 512 as the initial value",
+                format!("an expression which is not constant for {}", name)
+            );
+        }
+        let value = self.emit_expr(value)?;
+        let initializer = self.build_cast_value(value, value_type.to_basic_type(self.context))?;
+        let qualified_name = self.define(name);
+        let global = self.module.add_global(
+            value_type.to_basic_type(self.context),
+            None,
+            &qualified_name,
+        );
+        global.set_linkage(Linkage::Internal);
+        global.set_section(EEPROM_SECTION);
+        global.set_initializer(&initializer);
+        self.persistent_globals
+            .set(&qualified_name, (value_type, global.as_pointer_value()));
+        Ok(())
+    }
+
+    /// Read a persistent variable from the EEPROM
+    pub fn build_persistent_read(
+        &self,
+        value_type: ValueType,
+        pointer: PointerValue<'ctx>,
+        name: &str,
+    ) -> Result<Value<'ctx>, LLVMCompileError> {
+        let buffer = self.build_entry_alloca(value_type.to_basic_type(self.context), name)?;
+        self.build_persistent_call(PERSISTENT_READ_FUNCTION, value_type, buffer, pointer);
+        let value = self.builder.build_load(buffer, name);
+        Ok(Value::from_basic_value(value_type, value))
+    }
+
+    /// Write a persistent variable to the EEPROM. Unchanged bytes are not written
+    /// to save the lifetime of the EEPROM.
+    pub fn build_persistent_write(
+        &self,
+        value: Value<'ctx>,
+        value_type: ValueType,
+        pointer: PointerValue<'ctx>,
+        name: &str,
+    ) -> Result<(), LLVMCompileError> {
+        let value = self.build_cast_value(value, value_type.to_basic_type(self.context))?;
+        let buffer = self.build_entry_alloca(value_type.to_basic_type(self.context), name)?;
+        self.builder.build_store(buffer, value);
+        self.build_persistent_call(PERSISTENT_WRITE_FUNCTION, value_type, buffer, pointer);
+        Ok(())
+    }
+
+    fn build_persistent_call(
+        &self,
+        name: &str,
+        value_type: ValueType,
+        buffer: PointerValue<'ctx>,
+        pointer: PointerValue<'ctx>,
+    ) {
+        let function = self
+            .get_function(name)
+            .expect(&format!("'{}' is not declared", name));
+        let data = self.builder.build_pointer_cast(
+            buffer,
+            self.context.i8_type().ptr_type(AddressSpace::Generic),
+            "data",
+        );
+        // The lower 16 bits of the address in the section is the address in the EEPROM
+        let address = pointer.const_to_int(self.context.i16_type());
+        let size = self
+            .context
+            .i8_type()
+            .const_int(((value_type.get_bitwidth() + 7) / 8) as u64, false);
+        self.builder
+            .build_call(function, &[data.into(), address.into(), size.into()], "");
+    }
+}
+
+/// Check if an expression is a literal such as `512`, `-1.5` or `True`
+fn is_literal(expr: &ast::Expression) -> bool {
+    match &expr.node {
+        ast::ExpressionType::Number { .. }
+        | ast::ExpressionType::True
+        | ast::ExpressionType::False => true,
+        ast::ExpressionType::Unop {
+            op: ast::UnaryOperator::Neg,
+            a,
+        } => matches!(a.node, ast::ExpressionType::Number { .. }),
+        _ => false,
+    }
+}
@@ -0,0 +1,328 @@
+use inkwell::module::Linkage;
+use inkwell::values::{BasicValueEnum, FloatValue};
+use inkwell::AddressSpace;
+
+use dsp_compiler_error::{err, LLVMCompileError, LLVMCompileErrorType};
+use dsp_compiler_mangler::get_mangled_name;
+use dsp_compiler_value::convert::try_get_constant_string;
+use dsp_compiler_value::value::{Value, ValueTypeGroup};
+use dsp_python_parser::ast;
+
+use crate::CodeGen;
+
+/// The functions compiled by the code generator instead of being called
+pub const PRINT_FUNCTIONS: [&str; 2] = ["print", "println"];
+
+/// The wrapper which selects the serial port written by the print functions
+const PRINT_SELECT_FUNCTION: &str = "print_select";
+
+/// The wrapper which prints a string in the program memory
+const PRINT_FLASH_FUNCTION: &str = "print_flash";
+
+/// The address space of the program memory of AVR, `P1` in the data layout
+const PROGRAM_ADDRESS_SPACE: AddressSpace = AddressSpace::Global;
+
+impl<'a, 'ctx> CodeGen<'a, 'ctx> {
+    /// Compile `print(*values, sep=" ", end="\n")`.
+    /// `println` is the same as `print` except that its default `end` is `"\r\n"`.
+    pub fn compile_print(
+        &mut self,
+        args: &[ast::Expression],
+        keywords: &[ast::Keyword],
+        newline: bool,
+    ) -> Result<Value<'ctx>, LLVMCompileError> {
+        // Printing is only possible inside functions
+        self.get_fn_value()?;
+
+        let mut sep = " ".to_string();
+        let mut end = if newline { "\r\n" } else { "\n" }.to_string();
+        for keyword in keywords.iter() {
+            self.set_loc(keyword.value.location);
+            let value = match &keyword.value.node {
+                ast::ExpressionType::String { value } => try_get_constant_string(value),
+                _ => None,
+            };
+            let value = match value {
+                Some(value) => value,
+                None => {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::NotImplemented,
+                        "The keyword arguments of print must be string literals."
+                    );
+                }
+            };
+            match keyword.name.as_deref() {
+                Some("sep") => sep = value,
+                Some("end") => end = value,
+                _ => {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::SyntaxError,
+                        format!(
+                            "'{}' is an invalid keyword argument for print()",
+                            keyword.name.as_deref().unwrap_or("**")
+                        )
+                    );
+                }
+            }
+        }
+
+        for (i, arg) in args.iter().enumerate() {
+            if i > 0 {
+                self.build_print_str(&sep);
+            }
+            match &arg.node {
+                // f-strings are expanded into a series of print calls at compile time
+                ast::ExpressionType::String { value }
+                    if try_get_constant_string(value).is_none() =>
+                {
+                    self.compile_fstring_part(value)?;
+                }
+                ast::ExpressionType::String { value } => {
+                    let string = try_get_constant_string(value).unwrap();
+                    self.build_print_str(&string);
+                }
+                _ => {
+                    let value = self.emit_expr(arg)?;
+                    self.compile_formatted_print(value, "")?;
+                }
+            }
+        }
+        self.build_print_str(&end);
+
+        Ok(Value::Void)
+    }
+
+    /// Compile `port.print(...)` which prints to the serial port of an object
+    /// by switching the output of the print functions while printing
+    pub fn compile_print_to(
+        &mut self,
+        object: &ast::Expression,
+        args: &[ast::Expression],
+        keywords: &[ast::Keyword],
+        newline: bool,
+    ) -> Result<Value<'ctx>, LLVMCompileError> {
+        self.get_fn_value()?;
+        let handle = self.emit_expr(object)?;
+        let handle = self.build_cast_value(handle, self.context.i8_type().into())?;
+        self.build_print_call(PRINT_SELECT_FUNCTION, &[handle]);
+        self.compile_print(args, keywords, newline)?;
+        // The default output is the port 0
+        self.build_print_call(
+            PRINT_SELECT_FUNCTION,
+            &[BasicValueEnum::IntValue(
+                self.context.i8_type().const_zero(),
+            )],
+        );
+        Ok(Value::Void)
+    }
+
+    fn compile_fstring_part(&mut self, string: &ast::StringGroup) -> Result<(), LLVMCompileError> {
+        match string {
+            ast::StringGroup::Constant { value } => {
+                self.build_print_str(value);
+            }
+            ast::StringGroup::Joined { values } => {
+                for value in values.iter() {
+                    self.compile_fstring_part(value)?;
+                }
+            }
+            ast::StringGroup::FormattedValue {
+                value,
+                conversion,
+                spec,
+            } => {
+                if conversion.is_some() {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::NotImplemented,
+                        "Conversion flags in f-strings are not supported."
+                    );
+                }
+                let spec = match spec {
+                    Some(spec) => match try_get_constant_string(spec) {
+                        Some(spec) => spec,
+                        None => {
+                            return err!(
+                                self,
+                                LLVMCompileErrorType::NotImplemented,
+                                "Nested format specifications are not supported."
+                            );
+                        }
+                    },
+                    None => String::new(),
+                };
+                let value = self.emit_expr(value)?;
+                self.compile_formatted_print(value, &spec)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Print a value according to a subset of the format specification mini-language
+    fn compile_formatted_print(
+        &mut self,
+        value: Value<'ctx>,
+        spec: &str,
+    ) -> Result<(), LLVMCompileError> {
+        if spec.is_empty() {
+            return self.build_print_value(value);
+        }
+
+        let value_type = value.get_type();
+        match value_type.get_group() {
+            ValueTypeGroup::Int | ValueTypeGroup::UInt => {
+                let (base, upper) = match spec {
+                    "d" => return self.build_print_value(value),
+                    "x" => (16, false),
+                    "X" => (16, true),
+                    "o" => (8, false),
+                    "b" => (2, false),
+                    _ if spec.ends_with('f') => {
+                        let cast = self.build_cast_value(value, self.context.f32_type().into())?;
+                        return self.compile_formatted_float_print(cast.into_float_value(), spec);
+                    }
+                    _ => {
+                        return err!(
+                            self,
+                            LLVMCompileErrorType::NotImplemented,
+                            format!("Unsupported format specification '{}' for int", spec)
+                        );
+                    }
+                };
+
+                // Integers are widened to 32 bits to be printed in the other bases
+                let function = if value_type.get_group() == ValueTypeGroup::UInt {
+                    "print_base__ul__"
+                } else {
+                    "print_base__l__"
+                };
+                let value = self.build_cast_value(value, self.context.i32_type().into())?;
+                self.build_print_call(
+                    function,
+                    &[
+                        value,
+                        BasicValueEnum::IntValue(self.context.i8_type().const_int(base, false)),
+                        BasicValueEnum::IntValue(
+                            self.context.i8_type().const_int(upper as u64, false),
+                        ),
+                    ],
+                );
+            }
+            ValueTypeGroup::Float => {
+                let value = self.build_cast_value(value, self.context.f32_type().into())?;
+                self.compile_formatted_float_print(value.into_float_value(), spec)?;
+            }
+            _ => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::NotImplemented,
+                    format!(
+                        "Unsupported format specification '{}' for {:?}",
+                        spec, value_type
+                    )
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Call the print function mangled with the type of the value
+    fn build_print_value(&mut self, value: Value<'ctx>) -> Result<(), LLVMCompileError> {
+        let name = get_mangled_name("print", &[value.get_type()]);
+        let function = match self.get_function(&name) {
+            Some(function) => function,
+            None => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::NotImplemented,
+                    format!("Printing {:?} is not supported.", value.get_type())
+                );
+            }
+        };
+        let param_type = function.get_type().get_param_types()[0];
+        let value = self.build_cast_value(value, param_type)?;
+        self.builder.build_call(function, &[value], "print");
+        Ok(())
+    }
+
+    fn compile_formatted_float_print(
+        &mut self,
+        value: FloatValue<'ctx>,
+        spec: &str,
+    ) -> Result<(), LLVMCompileError> {
+        let digits = match spec {
+            "" => {
+                self.build_print_call("print__f__", &[BasicValueEnum::FloatValue(value)]);
+                return Ok(());
+            }
+            // Python prints six digits after the decimal point by default
+            "f" => 6,
+            _ => match spec
+                .strip_prefix('.')
+                .and_then(|spec| spec.strip_suffix('f'))
+                .and_then(|digits| digits.parse::<u8>().ok())
+            {
+                Some(digits) => digits,
+                None => {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::NotImplemented,
+                        format!("Unsupported format specification '{}' for float", spec)
+                    );
+                }
+            },
+        };
+        self.build_print_call(
+            "print_digits__f__",
+            &[
+                BasicValueEnum::FloatValue(value),
+                BasicValueEnum::IntValue(self.context.i8_type().const_int(digits as u64, false)),
+            ],
+        );
+        Ok(())
+    }
+
+    /// Print a constant string unless it is empty.
+    /// The string is kept in the program memory instead of SRAM unless disabled.
+    fn build_print_str(&self, string: &str) {
+        if string.is_empty() {
+            return;
+        }
+        if !self.flash_strings {
+            let value = self
+                .builder
+                .build_global_string_ptr(string, ".str")
+                .as_pointer_value();
+            self.build_print_call("print__s__", &[BasicValueEnum::PointerValue(value)]);
+            return;
+        }
+
+        let value = self.context.const_string(string.as_bytes(), true);
+        let global =
+            self.module
+                .add_global(value.get_type(), Some(PROGRAM_ADDRESS_SPACE), ".str.flash");
+        global.set_linkage(Linkage::Private);
+        global.set_constant(true);
+        global.set_unnamed_addr(true);
+        global.set_initializer(&value);
+        // The wrapper takes the address as a pointer to data,
+        // which has the same width as a pointer to the program memory
+        let pointer = global
+            .as_pointer_value()
+            .const_to_int(self.context.i16_type())
+            .const_to_pointer(self.context.i8_type().ptr_type(AddressSpace::Generic));
+        self.build_print_call(
+            PRINT_FLASH_FUNCTION,
+            &[BasicValueEnum::PointerValue(pointer)],
+        );
+    }
+
+    fn build_print_call(&self, name: &str, args: &[BasicValueEnum<'ctx>]) {
+        let function = self
+            .get_function(name)
+            .expect(&format!("'{}' is not declared", name));
+        self.builder.build_call(function, args, "print");
+    }
+}
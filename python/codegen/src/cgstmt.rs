@@ -1,16 +1,22 @@
 use std::option::Option::Some;
 
-use inkwell::types::BasicTypeEnum;
-use inkwell::values::BasicValue;
+use inkwell::types::{BasicType, BasicTypeEnum, FunctionType};
+use inkwell::values::{BasicValue, BasicValueEnum};
 use inkwell::{FloatPredicate, IntPredicate};
 
 use dsp_compiler_error::{err, LLVMCompileError, LLVMCompileErrorType};
+use dsp_compiler_mangler::get_mangled_name;
+use dsp_compiler_value::convert::try_get_constant_string;
 use dsp_compiler_value::value::{Value, ValueHandler, ValueType};
 use dsp_python_macros::*;
 use dsp_python_parser::ast;
 
-use crate::scope::LLVMVariableAccessor;
-use crate::{get_doc, CodeGen};
+use crate::cgpersistent::PERSISTENT_ANNOTATION;
+use crate::cgprint::PRINT_FUNCTIONS;
+use crate::scope::Class;
+use crate::vectors::{get_vector_number, AVR_SIGNAL_CALL_CONV};
+use crate::{get_doc, get_identifier, CodeGen, ENTRY_POINTS, OBJECT_HANDLE_TYPE};
+use inkwell::attributes::AttributeLoc;
 use inkwell::module::Linkage;
 
 impl<'a, 'ctx> CodeGen<'a, 'ctx> {
@@ -37,22 +43,132 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
                         "Async functions are not supported."
                     );
                 }
-                if decorator_list.len() > 0 {
+                let mut overload = false;
+                let mut interrupt = false;
+                let mut symbol = None;
+                let mut vector = None;
+                for decorator in decorator_list.iter() {
+                    match &decorator.node {
+                        // Unlike Python, every `@overload` function is an implementation
+                        ast::ExpressionType::Identifier { name } if name == "overload" => {
+                            overload = true;
+                        }
+                        // `@interrupt` handlers can be passed to `attach_interrupt`
+                        ast::ExpressionType::Identifier { name } if name == "interrupt" => {
+                            interrupt = true;
+                        }
+                        // `@extern("millis")` declares a C function under the given symbol
+                        ast::ExpressionType::Call {
+                            function,
+                            args: decorator_args,
+                            keywords: decorator_keywords,
+                        } if matches!(&function.node,
+                            ast::ExpressionType::Identifier { name } if name == "extern") =>
+                        {
+                            symbol = get_string_argument(decorator_args, decorator_keywords);
+                            if symbol.is_none() {
+                                return err!(
+                                    self,
+                                    LLVMCompileErrorType::SyntaxError,
+                                    "@extern takes the symbol of a C function as a string literal"
+                                );
+                            }
+                        }
+                        // `@isr("TIMER1_COMPA_vect")` defines the handler of an interrupt vector
+                        ast::ExpressionType::Call {
+                            function,
+                            args: decorator_args,
+                            keywords: decorator_keywords,
+                        } if matches!(&function.node,
+                            ast::ExpressionType::Identifier { name } if name == "isr") =>
+                        {
+                            vector = get_string_argument(decorator_args, decorator_keywords);
+                            if vector.is_none() {
+                                return err!(
+                                    self,
+                                    LLVMCompileErrorType::SyntaxError,
+                                    "@isr takes the name of an interrupt vector as a string literal"
+                                );
+                            }
+                        }
+                        _ => {
+                            return err!(
+                                self,
+                                LLVMCompileErrorType::NotImplemented,
+                                "Decorators are not implemented."
+                            );
+                        }
+                    }
+                }
+                if is_stub(body) {
+                    if interrupt || vector.is_some() {
+                        return err!(
+                            self,
+                            LLVMCompileErrorType::SyntaxError,
+                            "An interrupt handler must have a body"
+                        );
+                    }
+                    return self.compile_stmt_function_stub(
+                        name,
+                        args,
+                        returns,
+                        overload,
+                        symbol.as_deref(),
+                    );
+                }
+                if symbol.is_some() {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::SyntaxError,
+                        "The body of an @extern function must be '...'"
+                    );
+                }
+                self.compile_stmt_function_def(
+                    name,
+                    args,
+                    body,
+                    returns,
+                    overload,
+                    interrupt,
+                    vector.as_deref(),
+                )
+            }
+            StatementType::ClassDef {
+                name,
+                body,
+                bases,
+                keywords,
+                decorator_list,
+            } => {
+                if bases.len() > 1 || !keywords.is_empty() || !decorator_list.is_empty() {
                     return err!(
                         self,
                         LLVMCompileErrorType::NotImplemented,
-                        "Decorators are not implemented."
+                        "Classes with several bases or decorators are not supported."
                     );
+                }
+                let base = match bases.first() {
+                    Some(base) => match self.get_annotation_class(base) {
+                        Some(base) => Some(base),
+                        None => {
+                            return err!(
+                                self,
+                                LLVMCompileErrorType::NotImplemented,
+                                "The base of a class must be a class declared by a stub."
+                            );
+                        }
+                    },
+                    None => None,
                 };
-                self.compile_stmt_function_def(name, args, body, returns)
+                self.compile_stmt_class_stub(name, base, body)
             }
             StatementType::AnnAssign {
                 target,
-                annotation: _, // Do not check type; It will be done in compile time
+                annotation,
                 value,
             } => {
                 if let Some(value) = value {
-                    self.compile_stmt_ann_assign(target, value)?;
+                    self.compile_stmt_ann_assign(target, annotation, value)?;
                 }
                 Ok(())
             }
@@ -64,18 +180,52 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
                         "Variable unpacking is not implemented."
                     );
                 }
-                self.compile_stmt_assign(targets.first().unwrap(), value)
+                self.compile_stmt_assign(targets.first().unwrap(), value, None)
             }
             StatementType::Return { value } => self.compile_stmt_return(value),
+            StatementType::Global { names } => {
+                if self._fn_value.is_none() {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::SyntaxError,
+                        "'global' outside function"
+                    );
+                }
+                for name in names.iter() {
+                    let qualified_name = match self.resolve(name) {
+                        Some(qualified_name)
+                            if self.globals.load(&qualified_name).is_some()
+                                || self.persistent_globals.load(&qualified_name).is_some() =>
+                        {
+                            qualified_name
+                        }
+                        _ => return err!(self, LLVMCompileErrorType::NameError, name),
+                    };
+                    if self.compile_context.interrupt {
+                        self.volatile_globals.insert(qualified_name);
+                    }
+                    self.compile_context.global_names.insert(name.clone());
+                }
+                Ok(())
+            }
             StatementType::ImportFrom {
                 level,
                 module,
                 names,
             } => {
-                let _level = level;
-                let target = module.as_ref().expect("Unknown module name");
                 let _names = names;
-                if target.contains("arduino") {
+                // `from . import led` is valid Python, but modules are found by their absolute names
+                let target = match (level, module) {
+                    (0, Some(module)) => module,
+                    _ => {
+                        return err!(
+                            self,
+                            LLVMCompileErrorType::ImportError,
+                            "Relative imports are not supported. Import the module by its name."
+                        );
+                    }
+                };
+                if target.contains("arduino") || target == "typing" {
                     // Builtin
                 } else {
                     return err!(
@@ -111,7 +261,8 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
     fn compile_stmt_assign(
         &mut self,
         target: &ast::Expression,
-        value: &ast::Expression,
+        value_expr: &ast::Expression,
+        annotation: Option<ValueType>,
     ) -> Result<(), LLVMCompileError> {
         let name = match &target.node {
             ast::ExpressionType::Identifier { name } => name,
@@ -123,22 +274,89 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
                 );
             }
         };
-        let value = self.emit_expr(value)?;
+        // Objects are kept in variables, e.g. `port = SoftwareSerial(10, 11)`
+        let class = self.get_expr_class(value_expr);
+        if let Some(class) = &class {
+            self.set_object_class(name, class.clone());
+        }
+        let mut value = self.emit_expr(value_expr)?;
+        let stack_str = self.is_stack_str(value_expr, &value);
+        if let Some(value_type) = annotation {
+            // Convert the value to the annotated type
+            let cast = self.build_cast_value(value, value_type.to_basic_type(self.context))?;
+            value = Value::from_basic_value(value_type, cast);
+        }
         let value_type = value.get_type();
+        // The index of the str parameter which the value is
+        let borrowed_str = get_identifier(value_expr)
+            .and_then(|source| self.compile_context.borrowed_strs.get(source))
+            .copied();
 
         if let Some(fn_value) = &self._fn_value {
-            // Define the local
-            let llvm_var = self.locals.load(fn_value, name);
-            let pointer = if let Some(llvm_var) = llvm_var {
-                llvm_var.pointer_value()
-            } else {
-                self.builder
-                    .build_alloca(value_type.to_basic_type(self.context), name)
+            if self.compile_context.global_names.contains(name) {
+                if stack_str {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::NotImplemented,
+                        format!(
+                            "The concatenated str can not be stored to the global {} because its buffer is on the stack",
+                            name
+                        )
+                    );
+                }
+                // The callers must not pass a concatenated str to the parameter stored here
+                if let Some(index) = borrowed_str {
+                    self.escaping_str_params
+                        .entry(*fn_value)
+                        .or_default()
+                        .insert(index);
+                }
+                let qualified_name = self.resolve(name).unwrap();
+                if let Some(&(global_type, pointer)) = self.persistent_globals.load(&qualified_name)
+                {
+                    return self.build_persistent_write(value, global_type, pointer, name);
+                }
+                // Store to the global declared by `global`
+                let &(global_type, pointer) = self.globals.load(&qualified_name).unwrap();
+                let cast = self.build_cast_value(value, global_type.to_basic_type(self.context))?;
+                self.builder.build_store(pointer, cast);
+                return Ok(());
+            }
+
+            // Define the local, which keeps its type when it is assigned again
+            let (local_type, pointer) = match self.locals.load(fn_value, name) {
+                Some(&(local_type, pointer)) => (local_type, pointer),
+                None => {
+                    let pointer = self
+                        .builder
+                        .build_alloca(value_type.to_basic_type(self.context), name);
+                    self.locals.set(fn_value, name, (value_type, pointer));
+                    (value_type, pointer)
+                }
             };
-            self.builder.build_store(pointer, value.to_basic_value());
-            self.locals.set(fn_value, name, (value_type, pointer));
+            let cast = self.build_cast_value(value, local_type.to_basic_type(self.context))?;
+            self.builder.build_store(pointer, cast);
+            if stack_str {
+                self.compile_context.stack_strs.insert(name.to_string());
+            } else {
+                self.compile_context.stack_strs.remove(name);
+            }
+            if class.is_none() {
+                self.compile_context.local_classes.remove(name);
+            }
+            match borrowed_str {
+                Some(index) => {
+                    self.compile_context
+                        .borrowed_strs
+                        .insert(name.to_string(), index);
+                }
+                None => {
+                    self.compile_context.borrowed_strs.remove(name);
+                }
+            }
         } else {
             // Define the global
+            let name = &self.define(name);
             let global = self
                 .module
                 .add_global(value_type.to_basic_type(self.context), None, name);
@@ -155,100 +373,414 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
     fn compile_stmt_ann_assign(
         &mut self,
         target: &ast::Expression,
+        annotation: &ast::Expression,
         value: &ast::Expression,
     ) -> Result<(), LLVMCompileError> {
-        self.compile_stmt_assign(target, value)
+        // `threshold: Persistent[int] = 512`
+        if let ast::ExpressionType::Subscript { a, b } = &annotation.node {
+            if let ast::ExpressionType::Identifier { name } = &a.node {
+                if name == PERSISTENT_ANNOTATION {
+                    return self.compile_stmt_persistent(target, b, value);
+                }
+            }
+        }
+        let value_type = self.get_annotation_type(annotation)?;
+        self.compile_stmt_assign(target, value, Some(value_type))?;
+        // `Serial1: SerialPort = 1` gives the handle of an object
+        if let (Some(class), ast::ExpressionType::Identifier { name }) =
+            (self.get_annotation_class(annotation), &target.node)
+        {
+            self.set_object_class(name, class);
+        }
+        Ok(())
     }
 
-    fn compile_stmt_function_def(
+    /// Remember the class of the object held by a variable
+    fn set_object_class(&mut self, name: &str, class: String) {
+        let qualified_name = match &self._fn_value {
+            None => Some(self.qualify(name)),
+            Some(_) if self.compile_context.global_names.contains(name) => self.resolve(name),
+            Some(_) => {
+                self.compile_context
+                    .local_classes
+                    .insert(name.to_string(), class);
+                return;
+            }
+        };
+        if let Some(qualified_name) = qualified_name {
+            self.object_classes.insert(qualified_name, class);
+        }
+    }
+
+    /// Get the class named by an annotation such as `SerialPort` or `serial.SerialPort`
+    fn get_annotation_class(&self, annotation: &ast::Expression) -> Option<String> {
+        let qualified_name = match &annotation.node {
+            ast::ExpressionType::Identifier { name } => self.resolve(name)?,
+            ast::ExpressionType::Attribute { value, name } => match &value.node {
+                ast::ExpressionType::Identifier { name: alias } => {
+                    self.resolve_attribute(alias, name)?
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(qualified_name).filter(|name| self.classes.contains_key(name))
+    }
+
+    pub fn get_annotation_type(
+        &self,
+        annotation: &ast::Expression,
+    ) -> Result<ValueType, LLVMCompileError> {
+        // Objects are passed by their handles
+        if self.get_annotation_class(annotation).is_some() {
+            return Ok(OBJECT_HANDLE_TYPE);
+        }
+        let name = match &annotation.node {
+            ast::ExpressionType::Identifier { name } => name.as_str(),
+            ast::ExpressionType::None => "None",
+            // `Callable[[], None]`
+            ast::ExpressionType::Subscript { a, b: _ } => match &a.node {
+                ast::ExpressionType::Identifier { name } if name == "Callable" => "Callable",
+                _ => {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::NotImplemented,
+                        "Unrecognizable type"
+                    );
+                }
+            },
+            _ => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::NotImplemented,
+                    "Unrecognizable type"
+                );
+            }
+        };
+        match ValueType::from_annotation(name) {
+            Some(value_type) => Ok(value_type),
+            None => err!(
+                self,
+                LLVMCompileErrorType::NotImplemented,
+                format!("Unimplemented type {}", name)
+            ),
+        }
+    }
+
+    /// A function whose body is `...` declares a function defined in the wrappers,
+    /// or the C function of the given symbol
+    fn compile_stmt_function_stub(
         &mut self,
-        name: &String,
-        args: &Box<ast::Parameters>,
-        body: &ast::Suite,
+        name: &str,
+        args: &ast::Parameters,
         returns: &Option<ast::Expression>,
+        overload: bool,
+        symbol: Option<&str>,
     ) -> Result<(), LLVMCompileError> {
-        // The types and names of arguments
-        let mut args_vec: Vec<BasicTypeEnum> = vec![];
-        let mut arg_names: Vec<&String> = vec![];
-        for arg in args.args.iter() {
-            arg_names.push(&arg.arg);
-            if arg.annotation.is_none() {
+        // Print functions are compiled by the code generator
+        if PRINT_FUNCTIONS.contains(&name) {
+            self.declare(name, name);
+            return Ok(());
+        }
+
+        let (fn_type, arg_types) = self.get_function_type(&args.args, returns)?;
+        let emitted_name = match symbol {
+            Some(symbol) => symbol.to_string(),
+            None if overload => get_mangled_name(name, &arg_types),
+            None => name.to_string(),
+        };
+        match self.get_function(&emitted_name) {
+            Some(f) if f.get_type() != fn_type => {
                 return err!(
                     self,
-                    LLVMCompileErrorType::SyntaxError,
-                    "You must provide type hint for arguments"
+                    LLVMCompileErrorType::TypeError,
+                    format!("{:?}", f.get_type()),
+                    format!("{:?}", fn_type)
                 );
             }
-            let arg_type;
-            match &arg.annotation.as_ref().unwrap().node {
-                ast::ExpressionType::Identifier { name } => {
-                    arg_type = name;
-                }
+            Some(_) => {}
+            None => {
+                self.module.add_function(&emitted_name, fn_type, None);
+            }
+        }
+        self.set_return_type(&emitted_name, returns)?;
+        // The overloads are found by their original name
+        self.declare(name, symbol.unwrap_or(name));
+        Ok(())
+    }
+
+    /// A class whose methods are `@extern` stubs declares the functions of C objects.
+    /// The objects are handles which are passed to the functions as the first argument.
+    fn compile_stmt_class_stub(
+        &mut self,
+        name: &str,
+        base: Option<String>,
+        body: &ast::Suite,
+    ) -> Result<(), LLVMCompileError> {
+        let class = match base {
+            Some(base) => self.classes[&base].clone(),
+            None => Class::new(),
+        };
+        let class_name = self.define(name);
+        self.classes.insert(class_name.clone(), class);
+
+        let (body, _doc_string) = get_doc(body);
+        for statement in body.iter() {
+            self.set_loc(statement.location);
+            let (method_name, args, method_body, decorator_list, returns) = match &statement.node {
+                ast::StatementType::FunctionDef {
+                    name,
+                    args,
+                    body,
+                    decorator_list,
+                    returns,
+                    ..
+                } => (name, args, body, decorator_list, returns),
+                ast::StatementType::Pass => continue,
                 _ => {
                     return err!(
                         self,
                         LLVMCompileErrorType::NotImplemented,
-                        "Unrecognizable type"
+                        "Classes may only contain method stubs."
                     );
                 }
+            };
+            if !matches!(args.args.first(), Some(arg) if arg.arg == "self") {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::SyntaxError,
+                    format!(
+                        "The first parameter of {}.{} must be self",
+                        name, method_name
+                    )
+                );
             }
-            match arg_type.as_str() {
-                "int" => args_vec.push(self.context.i16_type().into()),
-                "float" => args_vec.push(self.context.f32_type().into()),
-                _ => {
+
+            // The print methods are compiled by the code generator
+            if PRINT_FUNCTIONS.contains(&method_name.as_str()) {
+                self.classes
+                    .get_mut(&class_name)
+                    .unwrap()
+                    .add_method(method_name, method_name);
+                continue;
+            }
+
+            let symbol = match decorator_list.as_slice() {
+                [decorator] if is_stub(method_body) => match &decorator.node {
+                    ast::ExpressionType::Call {
+                        function,
+                        args: decorator_args,
+                        keywords: decorator_keywords,
+                    } if matches!(&function.node,
+                        ast::ExpressionType::Identifier { name } if name == "extern") =>
+                    {
+                        get_string_argument(decorator_args, decorator_keywords)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            let symbol = match symbol {
+                Some(symbol) => symbol,
+                None => {
                     return err!(
                         self,
                         LLVMCompileErrorType::NotImplemented,
-                        format!("Unimplemented argument type {}", arg_type)
+                        "Methods must be @extern stubs."
                     );
                 }
+            };
+
+            // The handle of the object comes first
+            let (fn_type, _) = self.get_function_type(&args.args[1..], returns)?;
+            let mut param_types = vec![OBJECT_HANDLE_TYPE.to_basic_type(self.context)];
+            param_types.extend(fn_type.get_param_types());
+            let fn_type = match fn_type.get_return_type() {
+                Some(return_type) => return_type.fn_type(&param_types, false),
+                None => self.context.void_type().fn_type(&param_types, false),
+            };
+            match self.get_function(&symbol) {
+                Some(f) if f.get_type() != fn_type => {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::TypeError,
+                        format!("{:?}", f.get_type()),
+                        format!("{:?}", fn_type)
+                    );
+                }
+                Some(_) => {}
+                None => {
+                    self.module.add_function(&symbol, fn_type, None);
+                }
             }
+            self.set_return_type(&symbol, returns)?;
+            self.classes
+                .get_mut(&class_name)
+                .unwrap()
+                .add_method(method_name, &symbol);
         }
+        Ok(())
+    }
 
-        // The type to return value of this function
-        let return_type = if let Some(annotation) = returns {
-            match &annotation.node {
-                ast::ExpressionType::Identifier { name } => name.to_owned(),
-                ast::ExpressionType::None => "None".to_string(),
-                _ => {
+    /// Remember the type returned by a function with its signedness,
+    /// and the class if the function returns an object
+    fn set_return_type(
+        &mut self,
+        emitted_name: &str,
+        returns: &Option<ast::Expression>,
+    ) -> Result<(), LLVMCompileError> {
+        // The signedness of the returned value is not in the type of LLVM
+        let return_type = self.get_return_type(returns)?;
+        self.return_types
+            .insert(emitted_name.to_string(), return_type);
+        if let Some(class) = returns
+            .as_ref()
+            .and_then(|returns| self.get_annotation_class(returns))
+        {
+            self.return_classes.insert(emitted_name.to_string(), class);
+        }
+        Ok(())
+    }
+
+    fn get_return_type(
+        &self,
+        returns: &Option<ast::Expression>,
+    ) -> Result<ValueType, LLVMCompileError> {
+        match returns {
+            Some(annotation) => self.get_annotation_type(annotation),
+            None => Ok(ValueType::Void),
+        }
+    }
+
+    /// Get the type of a function and the types of its arguments from the annotations
+    fn get_function_type(
+        &self,
+        args: &[ast::Parameter],
+        returns: &Option<ast::Expression>,
+    ) -> Result<(FunctionType<'ctx>, Vec<ValueType>), LLVMCompileError> {
+        let mut arg_types: Vec<ValueType> = vec![];
+        for arg in args.iter() {
+            let arg_type = match &arg.annotation {
+                Some(annotation) => self.get_annotation_type(annotation)?,
+                None => {
                     return err!(
                         self,
-                        LLVMCompileErrorType::NotImplemented,
-                        "Unknown return annotation node"
+                        LLVMCompileErrorType::SyntaxError,
+                        "You must provide type hint for arguments"
                     );
                 }
+            };
+            if arg_type.is_void() {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::SyntaxError,
+                    "Argument can not be None"
+                );
             }
+            arg_types.push(arg_type);
+        }
+        let args_vec = arg_types
+            .iter()
+            .map(|arg_type| arg_type.to_basic_type(self.context))
+            .collect::<Vec<BasicTypeEnum>>();
+
+        // The type to return value of this function
+        let return_type = self.get_return_type(returns)?;
+        let fn_type = if return_type.is_void() {
+            self.context.void_type().fn_type(&args_vec, false)
         } else {
-            "None".to_string()
+            return_type
+                .to_basic_type(self.context)
+                .fn_type(&args_vec, false)
         };
 
-        let f = self.module.add_function(
-            name,
-            match return_type.as_str() {
-                // i8 for arduino builtins
-                "int8" => self.context.i8_type().fn_type(&args_vec, false),
+        Ok((fn_type, arg_types))
+    }
 
-                "int" => self.context.i16_type().fn_type(&args_vec, false),
-                "float" => self.context.f32_type().fn_type(&args_vec, false),
-                "None" => self.context.void_type().fn_type(&args_vec, false),
-                _ => {
+    fn compile_stmt_function_def(
+        &mut self,
+        name: &String,
+        args: &Box<ast::Parameters>,
+        body: &ast::Suite,
+        returns: &Option<ast::Expression>,
+        overload: bool,
+        interrupt: bool,
+        vector: Option<&str>,
+    ) -> Result<(), LLVMCompileError> {
+        // The types and names of arguments
+        let (fn_type, arg_types) = self.get_function_type(&args.args, returns)?;
+        let interrupt = interrupt || vector.is_some();
+        if interrupt && (!arg_types.is_empty() || fn_type.get_return_type().is_some()) {
+            return err!(
+                self,
+                LLVMCompileErrorType::TypeError,
+                "an interrupt handler without arguments returning None",
+                format!("{}({:?})", name, arg_types)
+            );
+        }
+        let arg_names = args
+            .args
+            .iter()
+            .map(|arg| &arg.arg)
+            .collect::<Vec<&String>>();
+
+        let f = if let Some(vector) = vector {
+            // The linker puts `__vector_N` into the interrupt vector table
+            let vector_number = match get_vector_number(&self.mcu, vector) {
+                Some(vector_number) => vector_number,
+                None => {
                     return err!(
                         self,
-                        LLVMCompileErrorType::NotImplemented,
-                        format!("Unknown return type {}", return_type)
+                        LLVMCompileErrorType::NameError,
+                        format!("{} on {}", vector, self.mcu)
                     );
                 }
-            },
-            None,
-        );
-        if !vec!["setup", "loop"].contains(&name.as_str()) {
-            f.set_linkage(Linkage::Internal);
-        }
+            };
+            let emitted_name = format!("__vector_{}", vector_number);
+            if self.get_function(&emitted_name).is_some() {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::SyntaxError,
+                    format!("The handler of {} is already defined", vector)
+                );
+            }
+            self.declare(name, &emitted_name);
+
+            // Save every register and return with `reti` without enabling interrupts
+            let f = self.module.add_function(&emitted_name, fn_type, None);
+            f.set_call_conventions(AVR_SIGNAL_CALL_CONV);
+            f.add_attribute(
+                AttributeLoc::Function,
+                self.context.create_string_attribute("signal", ""),
+            );
+            f
+        } else {
+            // Overloads are distinguished by the types of their arguments
+            let qualified_name = self.define(name);
+            let emitted_name = if overload {
+                get_mangled_name(&qualified_name, &arg_types)
+            } else {
+                qualified_name
+            };
+
+            let f = self.module.add_function(&emitted_name, fn_type, None);
+            if !ENTRY_POINTS.contains(&emitted_name.as_str()) {
+                f.set_linkage(Linkage::Internal);
+            }
+            f
+        };
+
+        self.set_return_type(&f.get_name().to_string_lossy(), returns)?;
 
         // Create an entry block
         let bb = self.context.append_basic_block(f, "");
         self.builder.position_at_end(bb);
         self.compile_context.returned = false;
+        self.compile_context.global_names.clear();
+        self.compile_context.stack_strs.clear();
+        self.compile_context.local_classes.clear();
+        self.compile_context.borrowed_strs.clear();
+        self.compile_context.interrupt = interrupt;
 
         // Create local scope
         self.set_fn_value(f);
@@ -257,23 +789,19 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         // Assign arguments
         for (i, bv) in f.get_param_iter().enumerate() {
             let arg_name = arg_names[i];
-            let v = if bv.is_int_value() {
-                bv.into_int_value().set_name(arg_name);
-                Value::I16 {
-                    value: bv.into_int_value(),
-                }
-            } else if bv.is_float_value() {
-                bv.into_float_value().set_name(arg_name);
-                Value::F32 {
-                    value: bv.into_float_value(),
+            match bv {
+                BasicValueEnum::IntValue(value) => value.set_name(arg_name),
+                BasicValueEnum::FloatValue(value) => value.set_name(arg_name),
+                BasicValueEnum::PointerValue(value) => value.set_name(arg_name),
+                _ => {
+                    return err!(
+                        self,
+                        LLVMCompileErrorType::NotImplemented,
+                        "Unimplemented function argument type"
+                    );
                 }
-            } else {
-                return err!(
-                    self,
-                    LLVMCompileErrorType::NotImplemented,
-                    "Unimplemented function argument type"
-                );
-            };
+            }
+            let v = Value::from_basic_value(arg_types[i], bv);
             let pointer = self
                 .builder
                 .build_alloca(v.get_type().to_basic_type(self.context), arg_name);
@@ -283,6 +811,21 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
                 arg_name,
                 (v.get_type(), pointer),
             );
+            if arg_types[i] == ValueType::Str {
+                self.compile_context
+                    .borrowed_strs
+                    .insert(arg_name.to_string(), i);
+            }
+            // `def send(port: SerialPort)` takes the handle of an object
+            if let Some(class) = args.args[i]
+                .annotation
+                .as_ref()
+                .and_then(|annotation| self.get_annotation_class(annotation))
+            {
+                self.compile_context
+                    .local_classes
+                    .insert(arg_name.to_string(), class);
+            }
         }
 
         let (body, _doc_string) = get_doc(body);
@@ -295,20 +838,14 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
             let frt = self.get_fn_value().unwrap().get_type().get_return_type();
             if let Some(frt) = frt {
                 if frt.is_int_type() {
-                    match frt.into_int_type().get_bit_width() {
-                        8 => {
-                            self.builder
-                                .build_return(Some(&self.context.i8_type().const_zero()));
-                        }
-                        16 => {
-                            self.builder
-                                .build_return(Some(&self.context.i16_type().const_zero()));
-                        }
-                        _ => unimplemented!(),
-                    }
+                    self.builder
+                        .build_return(Some(&frt.into_int_type().const_zero()));
                 } else if frt.is_float_type() {
                     self.builder
-                        .build_return(Some(&self.context.f32_type().const_zero()));
+                        .build_return(Some(&frt.into_float_type().const_zero()));
+                } else if frt.is_pointer_type() {
+                    self.builder
+                        .build_return(Some(&frt.into_pointer_type().const_null()));
                 }
             } else {
                 self.builder.build_return(None);
@@ -316,6 +853,7 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         }
 
         self._fn_value = None;
+        self.compile_context.interrupt = false;
         Ok(())
     }
 
@@ -446,6 +984,13 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         }
         if let Some(value) = value {
             let return_value = self.emit_expr(value)?;
+            if self.is_stack_str(value, &return_value) {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::NotImplemented,
+                    "The concatenated str can not be returned because its buffer is on the stack"
+                );
+            }
 
             if return_value.get_type() == ValueType::Void {
                 self.builder.build_return(None);
@@ -469,7 +1014,8 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
                 return_value.invoke_handler(
                     ValueHandler::new()
                         .handle_int(&|_, value| self.builder.build_return(Some(&value)))
-                        .handle_float(&|_, value| self.builder.build_return(Some(&value))),
+                        .handle_float(&|_, value| self.builder.build_return(Some(&value)))
+                        .handle_str(&|_, value| self.builder.build_return(Some(&value))),
                 );
             }
         } else {
@@ -479,3 +1025,73 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         Ok(())
     }
 }
+
+/// Get the symbols of the C functions declared by the stubs of a module,
+/// and whether each of them is mangled by the types of its arguments by `@overload`
+pub fn get_stub_symbols(statements: &[ast::Statement]) -> Vec<(String, bool)> {
+    let mut symbols = vec![];
+    for statement in statements.iter() {
+        match &statement.node {
+            ast::StatementType::FunctionDef {
+                name,
+                body,
+                decorator_list,
+                ..
+            } if is_stub(body) => {
+                let mut symbol = None;
+                let mut overload = false;
+                for decorator in decorator_list.iter() {
+                    match &decorator.node {
+                        ast::ExpressionType::Identifier { name } if name == "overload" => {
+                            overload = true;
+                        }
+                        ast::ExpressionType::Call {
+                            function,
+                            args,
+                            keywords,
+                        } if matches!(&function.node,
+                            ast::ExpressionType::Identifier { name } if name == "extern") =>
+                        {
+                            symbol = get_string_argument(args, keywords);
+                        }
+                        _ => {}
+                    }
+                }
+                // The symbol given by `@extern` is not mangled
+                symbols.push(match symbol {
+                    Some(symbol) => (symbol, false),
+                    None => (name.to_string(), overload),
+                });
+            }
+            // The methods of the classes
+            ast::StatementType::ClassDef { body, .. } => {
+                symbols.extend(get_stub_symbols(body));
+            }
+            _ => {}
+        }
+    }
+    symbols
+}
+
+/// Check if the body of a function is only `...` besides its docstring
+fn is_stub(body: &[ast::Statement]) -> bool {
+    match get_doc(body).0 {
+        [statement] => matches!(
+            &statement.node,
+            ast::StatementType::Expression { expression }
+                if matches!(expression.node, ast::ExpressionType::Ellipsis)
+        ),
+        _ => false,
+    }
+}
+
+/// Get the string literal passed to a decorator such as `@extern("millis")`
+fn get_string_argument(args: &[ast::Expression], keywords: &[ast::Keyword]) -> Option<String> {
+    match (args, keywords) {
+        ([arg], []) => match &arg.node {
+            ast::ExpressionType::String { value } => try_get_constant_string(value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
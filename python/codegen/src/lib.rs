@@ -1,26 +1,69 @@
+use std::collections::{HashMap, HashSet};
+
 use inkwell::builder::Builder;
 use inkwell::context::Context;
 use inkwell::module::Module;
-use inkwell::values::FunctionValue;
+use inkwell::types::{BasicType, BasicTypeEnum};
+use inkwell::values::{BasicValueEnum, FunctionValue, InstructionOpcode, PointerValue};
+use inkwell::AddressSpace;
 
 use dsp_compiler_error::{err, LLVMCompileError, LLVMCompileErrorType};
+use dsp_compiler_mangler::demangle;
 use dsp_compiler_value::convert::try_get_constant_string;
+use dsp_compiler_value::value::{Value, ValueType, ValueTypeGroup};
 use dsp_python_parser::ast;
 
-use crate::scope::{Locals, VariableMap};
+use crate::registers::get_registers;
+use crate::scope::{Class, LLVMVariableAccessor, Locals, Namespace, VariableMap};
 
 pub mod scope;
 
 pub mod cgexpr;
+pub mod cgpersistent;
+pub mod cgprint;
 pub mod cgstmt;
+pub mod registers;
+pub mod vectors;
+
+/// The name of the module given to the compiler
+pub const MAIN_MODULE: &str = "__main__";
+
+/// The module whose names are visible in every module without import
+pub const BUILTINS_MODULE: &str = "arduino.builtins";
+
+/// The functions called by the Arduino core which must keep their names
+pub const ENTRY_POINTS: [&str; 2] = ["setup", "loop"];
+
+/// The size of the stack buffer which holds the result of a string concatenation
+pub const STR_BUFFER_SIZE: u32 = 64;
+
+/// The type of the handles which identify the objects of the classes declared by stubs
+pub const OBJECT_HANDLE_TYPE: ValueType = ValueType::U8;
 
 pub struct CompileContext {
     returned: bool,
+    // Names declared by `global` in the current function
+    global_names: HashSet<String>,
+    // Whether the current function is an interrupt handler
+    interrupt: bool,
+    // Locals holding a concatenated str, whose buffer is on the stack of the current function
+    stack_strs: HashSet<String>,
+    // The classes of the objects held by the locals of the current function
+    local_classes: HashMap<String, String>,
+    // Locals holding a str parameter, by the index of the parameter
+    borrowed_strs: HashMap<String, usize>,
 }
 
 impl CompileContext {
     pub fn new() -> Self {
-        CompileContext { returned: false }
+        CompileContext {
+            returned: false,
+            global_names: HashSet::new(),
+            interrupt: false,
+            stack_strs: HashSet::new(),
+            local_classes: HashMap::new(),
+            borrowed_strs: HashMap::new(),
+        }
     }
 }
 
@@ -29,11 +72,32 @@ pub struct CodeGen<'a, 'ctx> {
     pub builder: &'a Builder<'ctx>,
     pub module: &'a Module<'ctx>,
     pub compile_context: CompileContext,
+    // The target microcontroller such as atmega328p
+    pub mcu: String,
+    // Whether the string literals printed by print are kept in the program memory
+    pub flash_strings: bool,
 
     _fn_value: Option<FunctionValue<'ctx>>,
     _current_source_location: ast::Location,
     globals: VariableMap<'ctx>,
+    // The types returned by the functions, which know the signedness
+    return_types: HashMap<String, ValueType>,
+    // Globals accessed by interrupt handlers and the I/O registers
+    volatile_globals: HashSet<String>,
+    // The classes declared by stubs, and the classes of the globals holding their objects
+    // and of the values returned by functions
+    classes: HashMap<String, Class>,
+    object_classes: HashMap<String, String>,
+    return_classes: HashMap<String, String>,
+    // The globals kept in the EEPROM
+    persistent_globals: VariableMap<'ctx>,
+    // The buffers of the concatenated strs on the stack
+    str_buffers: HashSet<PointerValue<'ctx>>,
+    // The indices of the str parameters which functions store to globals
+    escaping_str_params: HashMap<FunctionValue<'ctx>, HashSet<usize>>,
     locals: Locals<'ctx>,
+    namespaces: HashMap<String, Namespace>,
+    current_module: String,
 }
 
 impl<'a, 'ctx> CodeGen<'a, 'ctx> {
@@ -41,16 +105,32 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         context: &'ctx Context,
         builder: &'a Builder<'ctx>,
         module: &'a Module<'ctx>,
+        mcu: &str,
+        flash_strings: bool,
     ) -> Self {
         CodeGen {
             context,
             builder,
             module,
+            mcu: mcu.to_string(),
+            flash_strings,
             _fn_value: None,
             _current_source_location: ast::Location::default(),
             globals: VariableMap::new(),
+            return_types: HashMap::new(),
+            volatile_globals: HashSet::new(),
+            classes: HashMap::new(),
+            object_classes: HashMap::new(),
+            return_classes: HashMap::new(),
+            persistent_globals: VariableMap::new(),
+            str_buffers: HashSet::new(),
+            escaping_str_params: HashMap::new(),
             locals: Locals::new(),
-            compile_context: CompileContext { returned: false },
+            namespaces: vec![(MAIN_MODULE.to_string(), Namespace::new())]
+                .into_iter()
+                .collect(),
+            current_module: MAIN_MODULE.to_string(),
+            compile_context: CompileContext::new(),
         }
     }
 
@@ -59,6 +139,181 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         self.module.get_function(name)
     }
 
+    /// Define the I/O registers of the target MCU in the current module.
+    /// They are volatile globals at fixed addresses.
+    pub fn define_registers(&mut self) -> Result<(), LLVMCompileError> {
+        let registers = match get_registers(&self.mcu) {
+            Some(registers) => registers,
+            None => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::ImportError,
+                    format!("The registers of {} are not supported", self.mcu)
+                );
+            }
+        };
+
+        let pointer_type = self.context.i8_type().ptr_type(AddressSpace::Generic);
+        for (name, address) in registers.into_iter() {
+            let qualified_name = self.define(name);
+            let pointer = self
+                .context
+                .i16_type()
+                .const_int(address as u64, false)
+                .const_to_pointer(pointer_type);
+            self.globals.set(&qualified_name, (ValueType::U8, pointer));
+            self.volatile_globals.insert(qualified_name);
+        }
+        Ok(())
+    }
+
+    /// Make every load and store of the globals accessed by interrupt handlers volatile,
+    /// so that the main program does not cache them in registers
+    pub fn mark_volatile_globals(&self) {
+        let pointers = self
+            .volatile_globals
+            .iter()
+            .filter_map(|name| self.globals.load(name))
+            .map(|llvm_variable| llvm_variable.pointer_value())
+            .collect::<Vec<PointerValue>>();
+        if pointers.is_empty() {
+            return;
+        }
+
+        let mut function = self.module.get_first_function();
+        while let Some(f) = function {
+            for bb in f.get_basic_blocks() {
+                let mut instruction = bb.get_first_instruction();
+                while let Some(inst) = instruction {
+                    let pointer_index = match inst.get_opcode() {
+                        InstructionOpcode::Load => Some(0),
+                        InstructionOpcode::Store => Some(1),
+                        _ => None,
+                    };
+                    let pointer = pointer_index
+                        .and_then(|index| inst.get_operand(index))
+                        .and_then(|operand| operand.left());
+                    if let Some(BasicValueEnum::PointerValue(pointer)) = pointer {
+                        if pointers.contains(&pointer) {
+                            inst.set_volatile(true).unwrap();
+                        }
+                    }
+                    instruction = inst.get_next_instruction();
+                }
+            }
+            function = f.get_next_function();
+        }
+    }
+
+    /// Switch the module to compile and return the previous one
+    pub fn set_module(&mut self, module_name: &str) -> String {
+        self.namespaces
+            .entry(module_name.to_string())
+            .or_insert_with(Namespace::new);
+        std::mem::replace(&mut self.current_module, module_name.to_string())
+    }
+
+    /// Define a name in the current module and return the name to emit
+    pub fn define(&mut self, name: &str) -> String {
+        // `name__i__` is an overload of `name`
+        if let Some((origin, _)) = demangle(name) {
+            let qualified_origin = self.qualify(origin);
+            self.declare(origin, &qualified_origin);
+        }
+        let qualified_name = self.qualify(name);
+        self.declare(name, &qualified_name);
+        qualified_name
+    }
+
+    pub fn qualify(&self, name: &str) -> String {
+        if self.current_module == MAIN_MODULE && ENTRY_POINTS.contains(&name) {
+            name.to_string()
+        } else {
+            format!("{}.{}", self.current_module, name)
+        }
+    }
+
+    /// Bind a name in the current module to a symbol which is emitted elsewhere
+    pub fn declare(&mut self, name: &str, emitted_name: &str) {
+        self.namespaces
+            .get_mut(&self.current_module)
+            .unwrap()
+            .bind(name, emitted_name);
+    }
+
+    /// Get the emitted name which the given name refers to in the current module.
+    /// Names which are not defined nor imported are looked up in the builtins.
+    pub fn resolve(&self, name: &str) -> Option<String> {
+        self.namespaces[&self.current_module]
+            .resolve(name)
+            .or_else(|| self.namespaces.get(BUILTINS_MODULE)?.resolve(name))
+            .cloned()
+    }
+
+    /// Resolve `module.name` where `module` is an imported module in the current module
+    pub fn resolve_attribute(&self, alias: &str, name: &str) -> Option<String> {
+        let module_name = self.namespaces[&self.current_module].resolve_module(alias)?;
+        self.namespaces[module_name].resolve(name).cloned()
+    }
+
+    /// `import module as alias`
+    pub fn import_module(&mut self, module_name: &str, alias: &str) {
+        self.namespaces
+            .get_mut(&self.current_module)
+            .unwrap()
+            .bind_module(alias, module_name);
+    }
+
+    /// `from module import name as alias`
+    pub fn import_name(
+        &mut self,
+        module_name: &str,
+        name: &str,
+        alias: &str,
+    ) -> Result<(), LLVMCompileError> {
+        let qualified_name = match self.namespaces[module_name].resolve(name) {
+            Some(qualified_name) => qualified_name.clone(),
+            None => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::ImportError,
+                    format!("cannot import name '{}' from '{}'", name, module_name)
+                );
+            }
+        };
+        self.declare(alias, &qualified_name);
+        Ok(())
+    }
+
+    /// `from module import *`
+    pub fn import_all(&mut self, module_name: &str) {
+        let names = self.namespaces[module_name]
+            .names()
+            .filter(|(name, _)| !name.starts_with('_'))
+            .map(|(name, qualified_name)| (name.clone(), qualified_name.clone()))
+            .collect::<Vec<(String, String)>>();
+        let namespace = self.namespaces.get_mut(&self.current_module).unwrap();
+        for (name, qualified_name) in names.iter() {
+            namespace.bind(name, qualified_name);
+        }
+    }
+
+    /// Find the functions overloading the given name and the types of their parameters
+    pub fn get_overloads(&self, name: &str) -> Vec<(String, Vec<ValueType>)> {
+        let mut overloads = vec![];
+        let mut function = self.module.get_first_function();
+        while let Some(f) = function {
+            let mangled_name = f.get_name().to_string_lossy();
+            if let Some((origin, arg_types)) = demangle(&mangled_name) {
+                if origin == name {
+                    overloads.push((mangled_name.to_string(), arg_types));
+                }
+            }
+            function = f.get_next_function();
+        }
+        overloads
+    }
+
     pub fn set_fn_value(&mut self, fn_value: FunctionValue<'ctx>) {
         self._fn_value = Some(fn_value);
     }
@@ -74,6 +329,78 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
         }
     }
 
+    /// Allocate a variable in the entry block of the current function
+    /// so that allocations inside loops do not grow the stack.
+    pub fn build_entry_alloca<T: BasicType<'ctx>>(
+        &self,
+        ty: T,
+        name: &str,
+    ) -> Result<PointerValue<'ctx>, LLVMCompileError> {
+        let entry = self.get_fn_value()?.get_first_basic_block().unwrap();
+        let builder = self.context.create_builder();
+        match entry.get_first_instruction() {
+            Some(instruction) => builder.position_before(&instruction),
+            None => builder.position_at_end(entry),
+        }
+        Ok(builder.build_alloca(ty, name))
+    }
+
+    /// Convert a value to the given type, e.g. the type of a parameter
+    pub fn build_cast_value(
+        &self,
+        value: Value<'ctx>,
+        to: BasicTypeEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, LLVMCompileError> {
+        let value_type = value.get_type();
+        let group = value_type.get_group();
+        let cast = match (value.to_basic_value(), to) {
+            (BasicValueEnum::IntValue(value), BasicTypeEnum::IntType(to)) => {
+                let from_width = value.get_type().get_bit_width();
+                let to_width = to.get_bit_width();
+                let cast = if from_width > to_width {
+                    self.builder.build_int_truncate(value, to, "itrunc")
+                } else if from_width == to_width {
+                    value
+                } else if group == ValueTypeGroup::Int {
+                    self.builder.build_int_s_extend(value, to, "sext")
+                } else {
+                    // Booleans and unsigned integers
+                    self.builder.build_int_z_extend(value, to, "zext")
+                };
+                cast.into()
+            }
+            (BasicValueEnum::IntValue(value), BasicTypeEnum::FloatType(to)) => {
+                let cast = if group == ValueTypeGroup::Int {
+                    self.builder.build_signed_int_to_float(value, to, "sitofp")
+                } else {
+                    self.builder
+                        .build_unsigned_int_to_float(value, to, "uitofp")
+                };
+                cast.into()
+            }
+            (BasicValueEnum::FloatValue(value), BasicTypeEnum::FloatType(to)) => {
+                let cast = if value.get_type() == to {
+                    value
+                } else if value.get_type() == self.context.f64_type() {
+                    self.builder.build_float_trunc(value, to, "fptrunc")
+                } else {
+                    self.builder.build_float_ext(value, to, "fpext")
+                };
+                cast.into()
+            }
+            (BasicValueEnum::PointerValue(value), BasicTypeEnum::PointerType(_)) => value.into(),
+            _ => {
+                return err!(
+                    self,
+                    LLVMCompileErrorType::TypeError,
+                    format!("{:?}", to),
+                    format!("{:?}", value_type)
+                );
+            }
+        };
+        Ok(cast)
+    }
+
     pub fn set_loc(&mut self, location: ast::Location) {
         self._current_source_location = location;
     }
@@ -83,6 +410,14 @@ impl<'a, 'ctx> CodeGen<'a, 'ctx> {
     }
 }
 
+/// Get the name which an expression consists of
+pub fn get_identifier(expr: &ast::Expression) -> Option<&String> {
+    match &expr.node {
+        ast::ExpressionType::Identifier { name } => Some(name),
+        _ => None,
+    }
+}
+
 pub fn get_doc(body: &[ast::Statement]) -> (&[ast::Statement], Option<String>) {
     if let Some((val, body_rest)) = body.split_first() {
         if let ast::StatementType::Expression { ref expression } = val.node {
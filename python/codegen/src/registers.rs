@@ -0,0 +1,204 @@
+/// The data space addresses of the I/O registers shared by the supported MCUs.
+/// 16-bit registers are accessed by their low and high bytes.
+const COMMON_REGISTERS: [(&str, u16); 70] = [
+    ("PINB", 0x23),
+    ("DDRB", 0x24),
+    ("PORTB", 0x25),
+    ("PINC", 0x26),
+    ("DDRC", 0x27),
+    ("PORTC", 0x28),
+    ("PIND", 0x29),
+    ("DDRD", 0x2A),
+    ("PORTD", 0x2B),
+    ("TIFR0", 0x35),
+    ("TIFR1", 0x36),
+    ("PCIFR", 0x3B),
+    ("EIFR", 0x3C),
+    ("EIMSK", 0x3D),
+    ("GPIOR0", 0x3E),
+    ("EECR", 0x3F),
+    ("EEDR", 0x40),
+    ("EEARL", 0x41),
+    ("EEARH", 0x42),
+    ("GTCCR", 0x43),
+    ("TCCR0A", 0x44),
+    ("TCCR0B", 0x45),
+    ("TCNT0", 0x46),
+    ("OCR0A", 0x47),
+    ("OCR0B", 0x48),
+    ("GPIOR1", 0x4A),
+    ("GPIOR2", 0x4B),
+    ("SPCR", 0x4C),
+    ("SPSR", 0x4D),
+    ("SPDR", 0x4E),
+    ("ACSR", 0x50),
+    ("SMCR", 0x53),
+    ("MCUSR", 0x54),
+    ("MCUCR", 0x55),
+    ("SPMCSR", 0x57),
+    ("SPL", 0x5D),
+    ("SPH", 0x5E),
+    ("SREG", 0x5F),
+    ("WDTCSR", 0x60),
+    ("CLKPR", 0x61),
+    ("OSCCAL", 0x66),
+    ("PCICR", 0x68),
+    ("EICRA", 0x69),
+    ("PCMSK0", 0x6B),
+    ("TIMSK0", 0x6E),
+    ("TIMSK1", 0x6F),
+    ("ADCL", 0x78),
+    ("ADCH", 0x79),
+    ("ADCSRA", 0x7A),
+    ("ADCSRB", 0x7B),
+    ("ADMUX", 0x7C),
+    ("DIDR0", 0x7E),
+    ("DIDR1", 0x7F),
+    ("TCCR1A", 0x80),
+    ("TCCR1B", 0x81),
+    ("TCCR1C", 0x82),
+    ("TCNT1L", 0x84),
+    ("TCNT1H", 0x85),
+    ("ICR1L", 0x86),
+    ("ICR1H", 0x87),
+    ("OCR1AL", 0x88),
+    ("OCR1AH", 0x89),
+    ("OCR1BL", 0x8A),
+    ("OCR1BH", 0x8B),
+    ("TWBR", 0xB8),
+    ("TWSR", 0xB9),
+    ("TWAR", 0xBA),
+    ("TWDR", 0xBB),
+    ("TWCR", 0xBC),
+    ("TWAMR", 0xBD),
+];
+
+/// The registers only in the ATmega48/88/168/328 family
+const ATMEGA328_REGISTERS: [(&str, u16); 17] = [
+    ("TIFR2", 0x37),
+    ("PRR", 0x64),
+    ("PCMSK1", 0x6C),
+    ("PCMSK2", 0x6D),
+    ("TIMSK2", 0x70),
+    ("TCCR2A", 0xB0),
+    ("TCCR2B", 0xB1),
+    ("TCNT2", 0xB2),
+    ("OCR2A", 0xB3),
+    ("OCR2B", 0xB4),
+    ("ASSR", 0xB6),
+    ("UCSR0A", 0xC0),
+    ("UCSR0B", 0xC1),
+    ("UCSR0C", 0xC2),
+    ("UBRR0L", 0xC4),
+    ("UBRR0H", 0xC5),
+    ("UDR0", 0xC6),
+];
+
+/// The registers only in the ATmega640/1280/2560
+const ATMEGA2560_REGISTERS: [(&str, u16); 62] = [
+    ("PINA", 0x20),
+    ("DDRA", 0x21),
+    ("PORTA", 0x22),
+    ("PINE", 0x2C),
+    ("DDRE", 0x2D),
+    ("PORTE", 0x2E),
+    ("PINF", 0x2F),
+    ("DDRF", 0x30),
+    ("PORTF", 0x31),
+    ("PING", 0x32),
+    ("DDRG", 0x33),
+    ("PORTG", 0x34),
+    ("PINH", 0x100),
+    ("DDRH", 0x101),
+    ("PORTH", 0x102),
+    ("PINJ", 0x103),
+    ("DDRJ", 0x104),
+    ("PORTJ", 0x105),
+    ("PINK", 0x106),
+    ("DDRK", 0x107),
+    ("PORTK", 0x108),
+    ("PINL", 0x109),
+    ("DDRL", 0x10A),
+    ("PORTL", 0x10B),
+    ("TIFR2", 0x37),
+    ("TIFR3", 0x38),
+    ("TIFR4", 0x39),
+    ("TIFR5", 0x3A),
+    ("EICRB", 0x6A),
+    ("PCMSK1", 0x6C),
+    ("PCMSK2", 0x6D),
+    ("TIMSK2", 0x70),
+    ("TIMSK3", 0x71),
+    ("TIMSK4", 0x72),
+    ("TIMSK5", 0x73),
+    ("OCR1CL", 0x8C),
+    ("OCR1CH", 0x8D),
+    ("TCCR2A", 0xB0),
+    ("TCCR2B", 0xB1),
+    ("TCNT2", 0xB2),
+    ("OCR2A", 0xB3),
+    ("OCR2B", 0xB4),
+    ("ASSR", 0xB6),
+    ("UCSR0A", 0xC0),
+    ("UCSR0B", 0xC1),
+    ("UCSR0C", 0xC2),
+    ("UBRR0L", 0xC4),
+    ("UBRR0H", 0xC5),
+    ("UDR0", 0xC6),
+    ("UCSR1A", 0xC8),
+    ("UCSR1B", 0xC9),
+    ("UCSR1C", 0xCA),
+    ("UBRR1L", 0xCC),
+    ("UBRR1H", 0xCD),
+    ("UDR1", 0xCE),
+    ("UCSR2A", 0xD0),
+    ("UCSR2B", 0xD1),
+    ("UCSR2C", 0xD2),
+    ("UBRR2L", 0xD4),
+    ("UBRR2H", 0xD5),
+    ("UDR2", 0xD6),
+    ("PRR0", 0x64),
+];
+
+/// The registers only in the ATmega16U4/32U4
+const ATMEGA32U4_REGISTERS: [(&str, u16); 21] = [
+    ("PINE", 0x2C),
+    ("DDRE", 0x2D),
+    ("PORTE", 0x2E),
+    ("PINF", 0x2F),
+    ("DDRF", 0x30),
+    ("PORTF", 0x31),
+    ("TIFR3", 0x38),
+    ("TIFR4", 0x39),
+    ("EICRB", 0x6A),
+    ("TIMSK3", 0x71),
+    ("TIMSK4", 0x72),
+    ("OCR1CL", 0x8C),
+    ("OCR1CH", 0x8D),
+    ("UCSR1A", 0xC8),
+    ("UCSR1B", 0xC9),
+    ("UCSR1C", 0xCA),
+    ("UCSR1D", 0xCB),
+    ("UBRR1L", 0xCC),
+    ("UBRR1H", 0xCD),
+    ("UDR1", 0xCE),
+    ("PRR0", 0x64),
+];
+
+/// Get the I/O registers of the given MCU with their addresses in the data space
+pub fn get_registers(mcu: &str) -> Option<Vec<(&'static str, u16)>> {
+    let registers: &[(&str, u16)] = match mcu {
+        "atmega48" | "atmega48p" | "atmega88" | "atmega88p" | "atmega168" | "atmega168p"
+        | "atmega328" | "atmega328p" => &ATMEGA328_REGISTERS,
+        "atmega640" | "atmega1280" | "atmega2560" => &ATMEGA2560_REGISTERS,
+        "atmega16u4" | "atmega32u4" => &ATMEGA32U4_REGISTERS,
+        _ => return None,
+    };
+    Some(
+        COMMON_REGISTERS
+            .iter()
+            .chain(registers.iter())
+            .cloned()
+            .collect(),
+    )
+}
@@ -85,3 +85,65 @@ impl<'ctx> Locals<'ctx> {
         self.local_variables.insert(fn_value, VariableMap::new());
     }
 }
+
+/// The names visible in a Python module and the qualified names they refer to
+pub struct Namespace {
+    names: HashMap<String, String>,
+    modules: HashMap<String, String>,
+}
+
+impl Namespace {
+    pub fn new() -> Self {
+        Namespace {
+            names: HashMap::new(),
+            modules: HashMap::new(),
+        }
+    }
+
+    pub fn resolve(&self, name: &str) -> Option<&String> {
+        self.names.get(name)
+    }
+
+    pub fn bind(&mut self, name: &str, qualified_name: &str) {
+        self.names
+            .insert(name.to_string(), qualified_name.to_string());
+    }
+
+    pub fn resolve_module(&self, alias: &str) -> Option<&String> {
+        self.modules.get(alias)
+    }
+
+    pub fn bind_module(&mut self, alias: &str, module_name: &str) {
+        self.modules
+            .insert(alias.to_string(), module_name.to_string());
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.names.iter()
+    }
+}
+
+/// A class declared by a stub. Its objects are the handles of C objects
+/// which are passed to the methods as the first argument.
+/// A subclass starts with the methods of its base.
+#[derive(Clone)]
+pub struct Class {
+    // The symbols of the methods
+    methods: HashMap<String, String>,
+}
+
+impl Class {
+    pub fn new() -> Self {
+        Class {
+            methods: HashMap::new(),
+        }
+    }
+
+    pub fn get_method(&self, name: &str) -> Option<&String> {
+        self.methods.get(name)
+    }
+
+    pub fn add_method(&mut self, name: &str, symbol: &str) {
+        self.methods.insert(name.to_string(), symbol.to_string());
+    }
+}
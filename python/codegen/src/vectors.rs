@@ -0,0 +1,162 @@
+/// `CallingConv::AVR_SIGNAL` of LLVM
+pub const AVR_SIGNAL_CALL_CONV: u32 = 85;
+
+/// The interrupt vectors of the ATmega48/88/168/328 family
+const ATMEGA328_VECTORS: [&str; 25] = [
+    "INT0",
+    "INT1",
+    "PCINT0",
+    "PCINT1",
+    "PCINT2",
+    "WDT",
+    "TIMER2_COMPA",
+    "TIMER2_COMPB",
+    "TIMER2_OVF",
+    "TIMER1_CAPT",
+    "TIMER1_COMPA",
+    "TIMER1_COMPB",
+    "TIMER1_OVF",
+    "TIMER0_COMPA",
+    "TIMER0_COMPB",
+    "TIMER0_OVF",
+    "SPI_STC",
+    "USART_RX",
+    "USART_UDRE",
+    "USART_TX",
+    "ADC",
+    "EE_READY",
+    "ANALOG_COMP",
+    "TWI",
+    "SPM_READY",
+];
+
+/// The interrupt vectors of the ATmega640/1280/2560
+const ATMEGA2560_VECTORS: [&str; 56] = [
+    "INT0",
+    "INT1",
+    "INT2",
+    "INT3",
+    "INT4",
+    "INT5",
+    "INT6",
+    "INT7",
+    "PCINT0",
+    "PCINT1",
+    "PCINT2",
+    "WDT",
+    "TIMER2_COMPA",
+    "TIMER2_COMPB",
+    "TIMER2_OVF",
+    "TIMER1_CAPT",
+    "TIMER1_COMPA",
+    "TIMER1_COMPB",
+    "TIMER1_COMPC",
+    "TIMER1_OVF",
+    "TIMER0_COMPA",
+    "TIMER0_COMPB",
+    "TIMER0_OVF",
+    "SPI_STC",
+    "USART0_RX",
+    "USART0_UDRE",
+    "USART0_TX",
+    "ANALOG_COMP",
+    "ADC",
+    "EE_READY",
+    "TIMER3_CAPT",
+    "TIMER3_COMPA",
+    "TIMER3_COMPB",
+    "TIMER3_COMPC",
+    "TIMER3_OVF",
+    "USART1_RX",
+    "USART1_UDRE",
+    "USART1_TX",
+    "TWI",
+    "SPM_READY",
+    "TIMER4_CAPT",
+    "TIMER4_COMPA",
+    "TIMER4_COMPB",
+    "TIMER4_COMPC",
+    "TIMER4_OVF",
+    "TIMER5_CAPT",
+    "TIMER5_COMPA",
+    "TIMER5_COMPB",
+    "TIMER5_COMPC",
+    "TIMER5_OVF",
+    "USART2_RX",
+    "USART2_UDRE",
+    "USART2_TX",
+    "USART3_RX",
+    "USART3_UDRE",
+    "USART3_TX",
+];
+
+/// The interrupt vectors of the ATmega16U4/32U4. Empty names are reserved.
+const ATMEGA32U4_VECTORS: [&str; 42] = [
+    "INT0",
+    "INT1",
+    "INT2",
+    "INT3",
+    "",
+    "",
+    "INT6",
+    "",
+    "PCINT0",
+    "USB_GEN",
+    "USB_COM",
+    "WDT",
+    "",
+    "",
+    "",
+    "TIMER1_CAPT",
+    "TIMER1_COMPA",
+    "TIMER1_COMPB",
+    "TIMER1_COMPC",
+    "TIMER1_OVF",
+    "TIMER0_COMPA",
+    "TIMER0_COMPB",
+    "TIMER0_OVF",
+    "SPI_STC",
+    "USART1_RX",
+    "USART1_UDRE",
+    "USART1_TX",
+    "ANALOG_COMP",
+    "ADC",
+    "EE_READY",
+    "TIMER3_CAPT",
+    "TIMER3_COMPA",
+    "TIMER3_COMPB",
+    "TIMER3_COMPC",
+    "TIMER3_OVF",
+    "TWI",
+    "SPM_READY",
+    "TIMER4_COMPA",
+    "TIMER4_COMPB",
+    "TIMER4_COMPD",
+    "TIMER4_OVF",
+    "TIMER4_FPF",
+];
+
+fn get_vectors(mcu: &str) -> Option<&'static [&'static str]> {
+    match mcu {
+        "atmega48" | "atmega48p" | "atmega88" | "atmega88p" | "atmega168" | "atmega168p"
+        | "atmega328" | "atmega328p" => Some(&ATMEGA328_VECTORS),
+        "atmega640" | "atmega1280" | "atmega2560" => Some(&ATMEGA2560_VECTORS),
+        "atmega16u4" | "atmega32u4" => Some(&ATMEGA32U4_VECTORS),
+        _ => None,
+    }
+}
+
+/// Get the number of an interrupt vector named like `TIMER1_COMPA_vect` in avr-libc.
+/// `__vector_11` is accepted as well.
+pub fn get_vector_number(mcu: &str, name: &str) -> Option<usize> {
+    if let Some(number) = name.strip_prefix("__vector_") {
+        return number.parse().ok();
+    }
+
+    let name = name.strip_suffix("_vect")?;
+    get_vectors(mcu)?
+        .iter()
+        .position(|vector| !vector.is_empty() && *vector == name)
+        // The vector 0 is the reset
+        .map(|index| index + 1)
+}
@@ -1,4 +1,6 @@
 use std::process::{Command, Stdio};
+use std::thread::sleep;
+use std::time::Duration;
 
 use crate::utils::get_arduino_dir;
 
@@ -6,30 +8,71 @@ use crate::utils::get_arduino_dir;
 pub struct AVRDudeFlags {
     pub mcu: String,
     pub port: String,
+    // The protocol of the bootloader such as `arduino`
+    pub programmer: String,
     pub baudrate: u64,
+    // Reset the board into the bootloader by opening the port at 1200 baud
+    pub use_1200bps_touch: bool,
 }
 
 impl AVRDudeFlags {
-    pub fn new(mcu: String, port: &str, baudrate: u64) -> Self {
+    pub fn new(
+        mcu: String,
+        port: &str,
+        programmer: String,
+        baudrate: u64,
+        use_1200bps_touch: bool,
+    ) -> Self {
         AVRDudeFlags {
             mcu,
             port: port.to_string(),
+            programmer,
             baudrate,
+            use_1200bps_touch,
         }
     }
 }
 
+/// Reset a board with native USB such as Leonardo into its bootloader.
+/// The board resets when its port is opened at 1200 baud and closed.
+fn touch_1200bps(port: &str) {
+    let status = if cfg!(target_os = "windows") {
+        let device = format!("{}:", port);
+        Command::new("cmd")
+            .args(&["/C", "mode", device.as_str(), "BAUD=1200"])
+            .status()
+    } else if cfg!(target_os = "macos") {
+        Command::new("stty").args(&["-f", port, "1200"]).status()
+    } else {
+        Command::new("stty").args(&["-F", port, "1200"]).status()
+    };
+    if !matches!(status, Ok(status) if status.success()) {
+        eprintln!(
+            "WARNING: Failed to reset {} at 1200 baud. Press the reset button of the board.",
+            port
+        );
+    }
+    // Wait for the bootloader to start
+    sleep(Duration::from_secs(2));
+}
+
 /// Use avrdude to flash hex
 pub fn avrdude(target_path: &str, flags: AVRDudeFlags) {
     // Load the environmental variable: `ARDUINO_DIR`
     let arduino_dir = get_arduino_dir();
 
+    if flags.use_1200bps_touch {
+        touch_1200bps(&flags.port);
+    }
+
     // avrdude from Arduino IDE
     let avrdude_executable = format!("{}/{}", arduino_dir, "hardware/tools/avr/bin/avrdude");
 
     let config_file = format!("-C{}/{}", arduino_dir, "/hardware/tools/avr/etc/avrdude.conf");
     let mcu = format!("-p{}", flags.mcu);
     let port = format!("-P{}", flags.port);
+    let programmer = format!("-c{}", flags.programmer);
+    let baudrate = format!("-b{}", flags.baudrate);
     let memtype = format!("-Uflash:w:{}:i", target_path);
 
     let mut args = vec![
@@ -42,11 +85,11 @@ pub fn avrdude(target_path: &str, flags: AVRDudeFlags) {
         // micro controller unit
         &mcu,
         // programmer type
-        "-carduino",
+        &programmer,
         // serial port
         &port,
         // baudrate
-        "-b115200",
+        &baudrate,
         // Disable auto erase for flash memory
         "-D",
         // memory operation specification
@@ -1,17 +1,49 @@
-use std::fs::create_dir_all;
+use std::fs::{create_dir_all, remove_file};
+use std::path::Path;
 use std::process::{Command, Stdio};
 
+use crate::arduino::libraries::get_libraries;
+use crate::arduino::wrappers::get_wrapper_dir;
 use crate::get_arduino_dir;
 
 #[derive(Clone)]
 pub struct AVRCompilerFlags {
     pub cpu_f: u64,
     pub mcu: String,
+    // The variant of the board which defines the pins
+    pub variant: String,
+    // The macros defined for the board
+    pub defines: Vec<String>,
+    // Directory which contains the wrappers to use instead of the embedded ones
+    pub stdlib_path: Option<String>,
+    // User C/C++ sources to link with the program
+    pub sources: Vec<String>,
+    pub include_dirs: Vec<String>,
+    // The libraries of these modules are linked with the program
+    pub imported_modules: Vec<String>,
 }
 
 impl AVRCompilerFlags {
-    pub fn new(cpu_f: u64, mcu: String) -> Self {
-        AVRCompilerFlags { cpu_f, mcu }
+    pub fn new(
+        cpu_f: u64,
+        mcu: String,
+        variant: String,
+        defines: Vec<String>,
+        stdlib_path: Option<String>,
+        sources: Vec<String>,
+        include_dirs: Vec<String>,
+        imported_modules: Vec<String>,
+    ) -> Self {
+        AVRCompilerFlags {
+            cpu_f,
+            mcu,
+            variant,
+            defines,
+            stdlib_path,
+            sources,
+            include_dirs,
+            imported_modules,
+        }
     }
 }
 
@@ -31,7 +63,7 @@ pub fn avrgcc(object: &str, flags: AVRCompilerFlags) -> String {
 
     let mcu = format!("-mmcu={}", flags.mcu);
     let cpu_f = format!("-DF_CPU={}L", flags.cpu_f);
-    let gcc_flags = vec![
+    let mut gcc_flags = vec![
         "-c",
         "-g",
         "-Os",
@@ -41,19 +73,30 @@ pub fn avrgcc(object: &str, flags: AVRCompilerFlags) -> String {
         &mcu,
         &cpu_f,
         "-MMD",
-        "-DUSB_VID=null",
-        "-DUSB_PID=null",
         "-DARDUINO=106",
     ];
+    gcc_flags.extend(flags.defines.iter().map(String::as_str));
     let mut gpp_flags = gcc_flags.clone();
     gpp_flags.push("-fno-exceptions");
 
     // Headers
-    let include_files = vec![
+    let mut include_files = vec![
         format!("-I{}/hardware/arduino/avr/cores/arduino", arduino_dir),
-        format!("-I{}/hardware/arduino/avr/variants/standard", arduino_dir),
+        format!(
+            "-I{}/hardware/arduino/avr/variants/{}",
+            arduino_dir, flags.variant
+        ),
         format!("-I{}/hardware/tools/avr/avr/include", arduino_dir),
     ];
+    include_files.extend(flags.include_dirs.iter().map(|dir| format!("-I{}", dir)));
+
+    // Arduino libraries used by the program
+    let libraries = get_libraries(&flags.imported_modules);
+    include_files.extend(
+        libraries
+            .iter()
+            .map(|library| format!("-I{}/{}", arduino_dir, library.dir)),
+    );
 
     // Arduino headers
     let arduino_libs = format!("{}/hardware/arduino/avr/cores/arduino/", arduino_dir);
@@ -62,8 +105,13 @@ pub fn avrgcc(object: &str, flags: AVRCompilerFlags) -> String {
 
     // Create a directory contains build files
     create_dir_all(out_prefix).unwrap();
+    // The objects of the sources of the previous build must not remain in the archive
+    let _ = remove_file(format!("{}core.a", out_prefix));
+
+    // DSPython wrappers
+    let wrapper_dir = get_wrapper_dir(flags.stdlib_path.as_deref(), out_prefix);
 
-    let compile_commands = vec![
+    let mut compile_commands = vec![
         // Compile standard Arduino cores
         "{GCC} {GCC_FLAGS} {INCLUDE_FILES} {ARDUINO_LIBS}WInterrupts.c -o {OUT_PREFIX}WInterrupts.c.o",
         "{GCC} {GCC_FLAGS} {INCLUDE_FILES} {ARDUINO_LIBS}wiring.c -o {OUT_PREFIX}wiring.c.o",
@@ -87,12 +135,18 @@ pub fn avrgcc(object: &str, flags: AVRCompilerFlags) -> String {
         "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {ARDUINO_LIBS}PluggableUSB.cpp -o {OUT_PREFIX}PluggableUSB.cpp.o",
         "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {ARDUINO_LIBS}HardwareSerial0.cpp -o {OUT_PREFIX}HardwareSerial0.cpp.o",
         "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {ARDUINO_LIBS}HardwareSerial1.cpp -o {OUT_PREFIX}HardwareSerial1.cpp.o",
+        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {ARDUINO_LIBS}HardwareSerial2.cpp -o {OUT_PREFIX}HardwareSerial2.cpp.o",
+        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {ARDUINO_LIBS}HardwareSerial3.cpp -o {OUT_PREFIX}HardwareSerial3.cpp.o",
         "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {ARDUINO_LIBS}abi.cpp -o {OUT_PREFIX}abi.cpp.o",
 
         // Compile DSPython wrapper
-        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} include/Serial.cc -o {OUT_PREFIX}Serial.cc.o",
-        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} include/Builtins.cc -o {OUT_PREFIX}Builtins.cc.o",
-        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} include/LLVMArduinoBuiltins.cc -o {OUT_PREFIX}LLVMArduinoBuiltins.cc.o",
+        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {WRAPPER_DIR}Serial.cc -o {OUT_PREFIX}Serial.cc.o",
+        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {WRAPPER_DIR}Serial1.cc -o {OUT_PREFIX}Serial1.cc.o",
+        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {WRAPPER_DIR}Serial2.cc -o {OUT_PREFIX}Serial2.cc.o",
+        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {WRAPPER_DIR}Serial3.cc -o {OUT_PREFIX}Serial3.cc.o",
+        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {WRAPPER_DIR}Builtins.cc -o {OUT_PREFIX}Builtins.cc.o",
+        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {WRAPPER_DIR}Str.cc -o {OUT_PREFIX}Str.cc.o",
+        "{GPP} {GPP_FLAGS} {INCLUDE_FILES} {WRAPPER_DIR}LLVMArduinoBuiltins.cc -o {OUT_PREFIX}LLVMArduinoBuiltins.cc.o",
 
         // Archiver
         "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}WInterrupts.c.o",
@@ -117,36 +171,102 @@ pub fn avrgcc(object: &str, flags: AVRCompilerFlags) -> String {
         "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}PluggableUSB.cpp.o",
         "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}HardwareSerial0.cpp.o",
         "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}HardwareSerial1.cpp.o",
+        "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}HardwareSerial2.cpp.o",
+        "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}HardwareSerial3.cpp.o",
         "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}abi.cpp.o",
 
         // Link DSPython wrapper library
         "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}Serial.cc.o",
+        "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}Serial1.cc.o",
+        "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}Serial2.cc.o",
+        "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}Serial3.cc.o",
         "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}Builtins.cc.o",
+        "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}Str.cc.o",
         "{AR} rcs {OUT_PREFIX}core.a {OUT_PREFIX}LLVMArduinoBuiltins.cc.o",
+    ]
+    .into_iter()
+    .map(|command| command.split(' ').map(String::from).collect())
+    .collect::<Vec<Vec<String>>>();
 
-        // Compile everything together
-        "{GCC} -w -Os -g -flto -fuse-linker-plugin -Wl,--gc-sections -mmcu={MCU} -o {INPUT}.elf {INPUT} {OUT_PREFIX}core.a -lm",
-        "{OBJCOPY} -O ihex -j .eeprom --set-section-flags=.eeprom=alloc,load --no-change-warnings --change-section-lma .eeprom=0 {INPUT}.elf {INPUT}.eep",
-        "{OBJCOPY} -O ihex -R .eeprom {INPUT}.elf {INPUT}.hex",
-    ];
+    // Compile and link the libraries and user sources
+    let mut sources = vec![];
+    for library in libraries.iter() {
+        sources.extend(
+            library
+                .sources
+                .iter()
+                .map(|source| format!("{}/{}/{}", arduino_dir, library.dir, source)),
+        );
+        sources.push(format!("{}{}", wrapper_dir, library.wrapper));
+    }
+    sources.extend(flags.sources.iter().cloned());
+    // The objects are numbered not to collide with each other or the objects of the core,
+    // e.g. `a/util.c` and `b/util.c`, or `wiring.c` of a project
+    let sources_prefix = format!("{}sources/", out_prefix);
+    create_dir_all(&sources_prefix).unwrap();
+    for (index, source) in sources.iter().enumerate() {
+        let file_name = Path::new(source).file_name().unwrap().to_str().unwrap();
+        let object = format!("{}{}-{}.o", sources_prefix, index, file_name);
+        let (compiler, compiler_flags) = if source.ends_with(".c") {
+            ("{GCC}", "{GCC_FLAGS}")
+        } else {
+            ("{GPP}", "{GPP_FLAGS}")
+        };
+        // The paths are single arguments even if they contain spaces
+        compile_commands.push(vec![
+            compiler.to_string(),
+            compiler_flags.to_string(),
+            "{INCLUDE_FILES}".to_string(),
+            source.clone(),
+            "-o".to_string(),
+            object.clone(),
+        ]);
+        compile_commands.push(vec![
+            "{AR}".to_string(),
+            "rcs".to_string(),
+            "{OUT_PREFIX}core.a".to_string(),
+            object,
+        ]);
+    }
+
+    // Compile everything together
+    compile_commands.extend(
+        vec![
+            "{GCC} -w -Os -g -flto -fuse-linker-plugin -Wl,--gc-sections -mmcu={MCU} -o {INPUT}.elf {INPUT} {OUT_PREFIX}core.a -lm",
+            "{OBJCOPY} -O ihex -j .eeprom --set-section-flags=.eeprom=alloc,load --no-change-warnings --change-section-lma .eeprom=0 {INPUT}.elf {INPUT}.eep",
+            "{OBJCOPY} -O ihex -R .eeprom {INPUT}.elf {INPUT}.hex",
+        ]
+        .into_iter()
+        .map(|command| command.split(' ').map(String::from).collect()),
+    );
 
     for command in compile_commands {
-        let command_string = command
-            .replace("{GCC}", &gcc_executable)
-            .replace("{GCC_FLAGS}", &gcc_flags.join(" "))
-            .replace("{INCLUDE_FILES}", &include_files.join(" "))
-            .replace("{ARDUINO_LIBS}", &arduino_libs)
-            .replace("{OUT_PREFIX}", out_prefix)
-            .replace("{GPP}", &gpp_executable)
-            .replace("{GPP_FLAGS}", &gpp_flags.join(" "))
-            .replace("{AR}", &ar_executable)
-            .replace("{MCU}", &flags.mcu)
-            .replace("{OBJCOPY}", &objcopy_executable)
-            .replace("{INPUT}", object);
-        let mut args = command_string.as_str().split(" ").collect::<Vec<&str>>();
+        // The commands are split into the arguments before the paths are substituted,
+        // so the paths containing spaces are passed as they are
+        let mut args: Vec<String> = vec![];
+        for token in command.iter() {
+            match token.as_str() {
+                "{GCC_FLAGS}" => args.extend(gcc_flags.iter().map(|flag| flag.to_string())),
+                "{GPP_FLAGS}" => args.extend(gpp_flags.iter().map(|flag| flag.to_string())),
+                "{INCLUDE_FILES}" => args.extend(include_files.iter().cloned()),
+                _ => args.push(
+                    token
+                        .replace("{GCC}", &gcc_executable)
+                        .replace("{ARDUINO_LIBS}", &arduino_libs)
+                        .replace("{WRAPPER_DIR}", &wrapper_dir)
+                        .replace("{OUT_PREFIX}", out_prefix)
+                        .replace("{GPP}", &gpp_executable)
+                        .replace("{AR}", &ar_executable)
+                        .replace("{MCU}", &flags.mcu)
+                        .replace("{OBJCOPY}", &objcopy_executable)
+                        .replace("{INPUT}", object),
+                ),
+            }
+        }
+        let command_string = args.join(" ");
 
         let mut process = if cfg!(target_os = "windows") {
-            args.insert(0, "/C");
+            args.insert(0, "/C".to_string());
             Command::new("cmd")
                 .args(args.as_slice())
                 .stdout(Stdio::inherit())
@@ -157,9 +277,8 @@ pub fn avrgcc(object: &str, flags: AVRCompilerFlags) -> String {
                     command_string.as_str()
                 ))
         } else {
-            args.insert(0, "-c");
-            Command::new("sh")
-                .args(args.as_slice())
+            Command::new(&args[0])
+                .args(&args[1..])
                 .stdout(Stdio::inherit())
                 .stdout(Stdio::inherit())
                 .spawn()
@@ -181,3 +300,40 @@ pub fn avrgcc(object: &str, flags: AVRCompilerFlags) -> String {
 
     return format!("{}.hex", object);
 }
+
+/// Get the sizes of the flash memory and SRAM used by a linked program
+pub fn get_memory_usage(elf: &str) -> (u64, u64) {
+    // avr-size from Arduino IDE
+    let size_executable = format!(
+        "{}/{}",
+        get_arduino_dir(),
+        "/hardware/tools/avr/bin/avr-size"
+    );
+    let output = Command::new(&size_executable)
+        .arg("-A")
+        .arg(elf)
+        .output()
+        .expect("Failed to execute avr-size!");
+
+    // Each line is `section size address`
+    let mut flash = 0;
+    let mut sram = 0;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let columns = line.split_whitespace().collect::<Vec<&str>>();
+        let size = match columns.get(1).and_then(|size| size.parse::<u64>().ok()) {
+            Some(size) => size,
+            None => continue,
+        };
+        match columns[0] {
+            ".text" => flash += size,
+            // The initial values of .data are copied from the flash memory
+            ".data" => {
+                flash += size;
+                sram += size;
+            }
+            ".bss" | ".noinit" => sram += size,
+            _ => {}
+        }
+    }
+    (flash, sram)
+}
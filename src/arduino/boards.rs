@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use crate::utils::find_arduino_dir;
+
+/// The settings of an Arduino board which the compiler, avr-gcc and avrdude depend on
+#[derive(Clone)]
+pub struct Board {
+    pub name: String,
+    pub mcu: String,
+    pub f_cpu: u64,
+    // The directory of `pins_arduino.h` in `hardware/arduino/avr/variants`
+    pub variant: String,
+    // The DSPython module which defines the pins such as `A0` and `LED_BUILTIN`
+    pub pin_module: String,
+    pub upload_protocol: String,
+    pub upload_speed: u64,
+    // The sizes of the memories in bytes available to a program
+    pub flash_size: u64,
+    pub sram_size: u64,
+    // `ARDUINO_AVR_UNO` is defined for `AVR_UNO`
+    pub build_board: String,
+    // The USB vendor and product IDs of the boards with native USB
+    pub usb_ids: Option<(u16, u16)>,
+    // The boards with native USB are reset into the bootloader by opening the port at 1200 baud
+    pub use_1200bps_touch: bool,
+}
+
+/// The board used unless `--board` is given
+pub const DEFAULT_BOARD: &str = "uno";
+
+/// The definitions of the AVR boards in the Arduino installation
+const BOARDS_FILE: &str = "hardware/arduino/avr/boards.txt";
+
+impl Board {
+    /// The macros which the Arduino core expects for the board
+    pub fn get_defines(&self) -> Vec<String> {
+        let mut defines = vec![
+            format!("-DARDUINO_{}", self.build_board),
+            "-DARDUINO_ARCH_AVR".to_string(),
+        ];
+        match self.usb_ids {
+            Some((vid, pid)) => {
+                defines.push(format!("-DUSB_VID={:#06x}", vid));
+                defines.push(format!("-DUSB_PID={:#06x}", pid));
+            }
+            None => {
+                defines.push("-DUSB_VID=null".to_string());
+                defines.push("-DUSB_PID=null".to_string());
+            }
+        }
+        defines
+    }
+}
+
+/// The boards which are known without an Arduino installation
+pub fn get_builtin_boards() -> Vec<Board> {
+    vec![
+        Board {
+            name: "uno".to_string(),
+            mcu: "atmega328p".to_string(),
+            f_cpu: 16000000,
+            variant: "standard".to_string(),
+            pin_module: "arduino.uno_pins".to_string(),
+            upload_protocol: "arduino".to_string(),
+            upload_speed: 115200,
+            flash_size: 32256,
+            sram_size: 2048,
+            build_board: "AVR_UNO".to_string(),
+            usb_ids: None,
+            use_1200bps_touch: false,
+        },
+        Board {
+            name: "nano".to_string(),
+            mcu: "atmega328p".to_string(),
+            f_cpu: 16000000,
+            // A6 and A7 are only analog inputs
+            variant: "eightanaloginputs".to_string(),
+            pin_module: "arduino.uno_pins".to_string(),
+            upload_protocol: "arduino".to_string(),
+            upload_speed: 115200,
+            flash_size: 30720,
+            sram_size: 2048,
+            build_board: "AVR_NANO".to_string(),
+            usb_ids: None,
+            use_1200bps_touch: false,
+        },
+        Board {
+            name: "mega".to_string(),
+            mcu: "atmega2560".to_string(),
+            f_cpu: 16000000,
+            variant: "mega".to_string(),
+            pin_module: "arduino.mega_pins".to_string(),
+            upload_protocol: "wiring".to_string(),
+            upload_speed: 115200,
+            flash_size: 253952,
+            sram_size: 8192,
+            build_board: "AVR_MEGA2560".to_string(),
+            usb_ids: None,
+            use_1200bps_touch: false,
+        },
+        Board {
+            name: "leonardo".to_string(),
+            mcu: "atmega32u4".to_string(),
+            f_cpu: 16000000,
+            variant: "leonardo".to_string(),
+            pin_module: "arduino.leonardo_pins".to_string(),
+            upload_protocol: "avr109".to_string(),
+            upload_speed: 57600,
+            flash_size: 28672,
+            sram_size: 2560,
+            build_board: "AVR_LEONARDO".to_string(),
+            usb_ids: Some((0x2341, 0x8036)),
+            use_1200bps_touch: true,
+        },
+        // The 5V model running at 16 MHz
+        Board {
+            name: "pro".to_string(),
+            mcu: "atmega328p".to_string(),
+            f_cpu: 16000000,
+            variant: "eightanaloginputs".to_string(),
+            pin_module: "arduino.uno_pins".to_string(),
+            upload_protocol: "arduino".to_string(),
+            upload_speed: 57600,
+            flash_size: 30720,
+            sram_size: 2048,
+            build_board: "AVR_PRO".to_string(),
+            usb_ids: None,
+            use_1200bps_touch: false,
+        },
+    ]
+}
+
+/// Find a board by its name and menu options such as `uno` or `nano:cpu=atmega328old`.
+/// The boards are read from `boards.txt` of the Arduino installation,
+/// or the built-in profiles are used if it does not exist or `ARDUINO_DIR` is not set.
+pub fn get_board(spec: &str) -> Result<Board, String> {
+    let (name, options) = parse_board_spec(spec)?;
+    let boards_file =
+        find_arduino_dir().map(|arduino_dir| Path::new(&arduino_dir).join(BOARDS_FILE));
+    match (boards_file.as_ref().map(read_to_string), &boards_file) {
+        (Some(Ok(source)), _) => {
+            get_board_from_properties(&parse_properties(&source), name, &options)
+        }
+        _ if options.is_empty() => get_builtin_boards()
+            .into_iter()
+            .find(|board| board.name == name)
+            .ok_or_else(|| format!("unknown board '{}'", name)),
+        (_, Some(boards_file)) => Err(format!(
+            "can't read '{}' to select the options of {}",
+            boards_file.display(),
+            name
+        )),
+        (_, None) => Err(format!(
+            "ARDUINO_DIR must be set to select the options of {}",
+            name
+        )),
+    }
+}
+
+/// Split `nano:cpu=atmega328old` into the name and the selected menu options
+fn parse_board_spec(spec: &str) -> Result<(&str, Vec<(&str, &str)>), String> {
+    let mut parts = spec.splitn(2, ':');
+    let name = parts.next().unwrap();
+    let mut options = vec![];
+    if let Some(option_list) = parts.next() {
+        for option in option_list.split(',') {
+            match option.find('=') {
+                Some(index) => options.push((&option[..index], &option[index + 1..])),
+                None => return Err(format!("expected 'menu=option', but found '{}'", option)),
+            }
+        }
+    }
+    Ok((name, options))
+}
+
+/// Parse the `key=value` lines of `boards.txt` in the order of the file
+fn parse_properties(source: &str) -> Vec<(String, String)> {
+    source
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let index = line.find('=')?;
+            Some((
+                line[..index].trim().to_string(),
+                line[index + 1..].trim().to_string(),
+            ))
+        })
+        .collect()
+}
+
+fn get_board_from_properties(
+    properties: &[(String, String)],
+    name: &str,
+    options: &[(&str, &str)],
+) -> Result<Board, String> {
+    let prefix = format!("{}.", name);
+    let entries = properties
+        .iter()
+        .filter_map(|(key, value)| Some((key.strip_prefix(&prefix)?, value.as_str())))
+        .collect::<Vec<(&str, &str)>>();
+    if entries.is_empty() {
+        return Err(format!("unknown board '{}'", name));
+    }
+
+    // Every menu selects its first option unless another one is given
+    let mut selected: Vec<(&str, &str)> = vec![];
+    for (key, _) in entries.iter() {
+        let menu_option = match key.strip_prefix("menu.") {
+            Some(menu_option) => menu_option.split('.').collect::<Vec<&str>>(),
+            None => continue,
+        };
+        if let [menu, option] = menu_option.as_slice() {
+            if !selected
+                .iter()
+                .any(|(selected_menu, _)| selected_menu == menu)
+            {
+                selected.push((*menu, *option));
+            }
+        }
+    }
+    for (menu, option) in options.iter() {
+        let option_key = format!("menu.{}.{}", menu, option);
+        match selected
+            .iter_mut()
+            .find(|(selected_menu, _)| selected_menu == menu)
+        {
+            Some(selection) if entries.iter().any(|(key, _)| *key == option_key) => {
+                selection.1 = *option;
+            }
+            Some(_) => return Err(format!("{} has no {} '{}'", name, menu, option)),
+            None => return Err(format!("{} has no menu '{}'", name, menu)),
+        }
+    }
+
+    // The properties of the selected options override the ones of the board
+    let mut values = entries
+        .iter()
+        .filter(|(key, _)| !key.starts_with("menu."))
+        .cloned()
+        .collect::<HashMap<&str, &str>>();
+    for (menu, option) in selected.iter() {
+        let option_prefix = format!("menu.{}.{}.", menu, option);
+        for (key, value) in entries.iter() {
+            if let Some(key) = key.strip_prefix(&option_prefix) {
+                values.insert(key, *value);
+            }
+        }
+    }
+
+    let get = |key: &str| {
+        values
+            .get(key)
+            .map(|value| value.to_string())
+            .ok_or_else(|| format!("{} does not define {}", name, key))
+    };
+    let get_number = |key: &str| {
+        let value = get(key)?;
+        // `16000000L` is a literal of C
+        value
+            .trim_end_matches('L')
+            .parse::<u64>()
+            .map_err(|_| format!("invalid {} of {}: {}", key, name, value))
+    };
+    let get_id = |key: &str| {
+        let value = get(key)?;
+        u16::from_str_radix(value.trim_start_matches("0x"), 16)
+            .map_err(|_| format!("invalid {} of {}: {}", key, name, value))
+    };
+
+    let variant = get("build.variant")?;
+    // The pins of the other variants would be numbered wrongly by the pins of Uno
+    let pin_module = match get_pin_module(&variant) {
+        Some(pin_module) => pin_module,
+        None => {
+            return Err(format!(
+                "the pins of the variant '{}' of {} are not supported",
+                variant, name
+            ))
+        }
+    };
+    let usb_ids = if values.contains_key("build.vid") && values.contains_key("build.pid") {
+        Some((get_id("build.vid")?, get_id("build.pid")?))
+    } else {
+        None
+    };
+    Ok(Board {
+        name: name.to_string(),
+        mcu: get("build.mcu")?,
+        f_cpu: get_number("build.f_cpu")?,
+        pin_module: pin_module.to_string(),
+        variant,
+        upload_protocol: get("upload.protocol")?,
+        upload_speed: get_number("upload.speed")?,
+        flash_size: get_number("upload.maximum_size")?,
+        sram_size: get_number("upload.maximum_data_size")?,
+        build_board: get("build.board")?,
+        usb_ids,
+        use_1200bps_touch: values.get("upload.use_1200bps_touch") == Some(&"true"),
+    })
+}
+
+/// Get the DSPython module of the pins defined by a variant
+fn get_pin_module(variant: &str) -> Option<&'static str> {
+    match variant {
+        "standard" | "eightanaloginputs" => Some("arduino.uno_pins"),
+        "mega" => Some("arduino.mega_pins"),
+        "leonardo" | "micro" => Some("arduino.leonardo_pins"),
+        _ => None,
+    }
+}
@@ -0,0 +1,63 @@
+/// An Arduino library which is compiled only if its module is imported
+pub struct Library {
+    // The DSPython module which binds the library
+    pub module: &'static str,
+    // The directory of the sources relative to `ARDUINO_DIR`
+    pub dir: &'static str,
+    pub sources: &'static [&'static str],
+    // The DSPython wrapper of the library
+    pub wrapper: &'static str,
+}
+
+pub const LIBRARIES: [Library; 6] = [
+    Library {
+        module: "arduino.eeprom",
+        dir: "hardware/arduino/avr/libraries/EEPROM/src",
+        // EEPROM is a header-only library
+        sources: &[],
+        wrapper: "EEPROM.cc",
+    },
+    Library {
+        module: "arduino.servo",
+        dir: "libraries/Servo/src",
+        sources: &["avr/Servo.cpp"],
+        wrapper: "Servo.cc",
+    },
+    Library {
+        module: "arduino.software_serial",
+        dir: "hardware/arduino/avr/libraries/SoftwareSerial/src",
+        sources: &["SoftwareSerial.cpp"],
+        wrapper: "SoftwareSerial.cc",
+    },
+    Library {
+        module: "arduino.spi",
+        dir: "hardware/arduino/avr/libraries/SPI/src",
+        sources: &["SPI.cpp"],
+        wrapper: "SPI.cc",
+    },
+    Library {
+        module: "arduino.timer",
+        dir: "hardware/arduino/avr/cores/arduino",
+        // Timer1 is configured by the wrapper
+        sources: &[],
+        wrapper: "Timer.cc",
+    },
+    Library {
+        module: "arduino.wire",
+        dir: "hardware/arduino/avr/libraries/Wire/src",
+        sources: &["Wire.cpp", "utility/twi.c"],
+        wrapper: "Wire.cc",
+    },
+];
+
+/// Get the libraries used by the imported modules
+pub fn get_libraries(imported_modules: &[String]) -> Vec<&'static Library> {
+    LIBRARIES
+        .iter()
+        .filter(|library| {
+            imported_modules
+                .iter()
+                .any(|module| module == library.module)
+        })
+        .collect()
+}
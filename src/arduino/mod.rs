@@ -1,2 +1,5 @@
 pub mod avrdude;
+pub mod boards;
 pub mod avrgcc;
+pub mod libraries;
+pub mod wrappers;
@@ -0,0 +1,23 @@
+use std::fs::{create_dir_all, write};
+use std::path::Path;
+
+/// The DSPython wrappers embedded in the compiler, which build.rs lists from `include`
+const WRAPPER_FILES: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/wrapper_files.rs"));
+
+/// Get the directory which contains the wrappers to compile.
+/// The embedded wrappers are written into the build directory
+/// unless the directory of the standard libraries is given.
+pub fn get_wrapper_dir(stdlib_path: Option<&str>, out_prefix: &str) -> String {
+    if let Some(stdlib_path) = stdlib_path {
+        return format!("{}/include/", stdlib_path);
+    }
+
+    let wrapper_dir = format!("{}include/", out_prefix);
+    create_dir_all(&wrapper_dir).unwrap();
+    for (name, source) in WRAPPER_FILES.iter() {
+        write(Path::new(&wrapper_dir).join(name), source)
+            .expect(&format!("Failed to write the wrapper '{}'", name));
+    }
+
+    wrapper_dir
+}
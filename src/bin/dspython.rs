@@ -1,10 +1,14 @@
 use std::error::Error;
-use std::fs::{remove_file, write, File};
+use std::fs::{remove_file, write};
+use std::path::Path;
 
 use clap::{App, Arg, ArgMatches};
 
 use dsp_compiler::{get_assembly, CompilerFlags};
-use dspython::{avrdude, avrgcc, static_compiler, AVRCompilerFlags, AVRDudeFlags};
+use dspython::{
+    avrdude, avrgcc, get_board, get_memory_usage, get_stdlib_path, static_compiler,
+    AVRCompilerFlags, AVRDudeFlags, Project, DEFAULT_BOARD, PROJECT_FILE,
+};
 
 const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 const AUTHORS: &'static str = env!("CARGO_PKG_AUTHORS");
@@ -18,11 +22,16 @@ fn parse_arguments<'a>(app: App<'a, '_>) -> ArgMatches<'a> {
         .long("--upload-to")
         .short("u")
         .takes_value(true);
+    let arg_board = Arg::with_name("board")
+        .help("The target board in boards.txt of Arduino, e.g. uno or nano:cpu=atmega328old")
+        .long("--board")
+        .takes_value(true)
+        .default_value(DEFAULT_BOARD);
     let arg_cpu = Arg::with_name("cpu")
+        .help("Use the given microcontroller instead of the one of the board")
         .long("--cpu")
         .short("c")
-        .takes_value(true)
-        .default_value("atmega328p");
+        .takes_value(true);
     let arg_opt = Arg::with_name("opt_level")
         .help("LLVM Optimization level. Must be in the range of 0 to 3")
         .long("--opt-level")
@@ -30,15 +39,47 @@ fn parse_arguments<'a>(app: App<'a, '_>) -> ArgMatches<'a> {
         .takes_value(true)
         .default_value("2");
     let arg_baudrate = Arg::with_name("baudrate")
-        .help("Serial communication speed")
+        .help("Upload speed instead of the one of the board")
         .long("--baudrate")
         .short("b")
-        .takes_value(true)
-        .default_value("9600");
+        .takes_value(true);
     let arg_remove_hex = Arg::with_name("remove_hex")
         .help("Remove generated hex file")
         .long("--remove-hex")
         .takes_value(false);
+    let arg_module_path = Arg::with_name("module_path")
+        .help("Add a directory to search for modules to import")
+        .long("--module-path")
+        .short("m")
+        .takes_value(true)
+        .number_of_values(1)
+        .multiple(true);
+    let arg_stdlib_path = Arg::with_name("stdlib_path")
+        .help("Use the standard libraries in the given directory instead of the embedded ones")
+        .long("--stdlib-path")
+        .takes_value(true);
+    let arg_source = Arg::with_name("source")
+        .help("Compile and link a C/C++ source file with the program")
+        .long("--source")
+        .short("s")
+        .takes_value(true)
+        .number_of_values(1)
+        .multiple(true);
+    let arg_include_dir = Arg::with_name("include_dir")
+        .help("Add a directory to search for headers of the C/C++ sources")
+        .long("--include-dir")
+        .short("I")
+        .takes_value(true)
+        .number_of_values(1)
+        .multiple(true);
+    let arg_project = Arg::with_name("project")
+        .help("Read the sources and include directories from a project file")
+        .long("--project")
+        .takes_value(true);
+    let arg_no_flash_strings = Arg::with_name("no_flash_strings")
+        .help("Keep the printed string literals in SRAM instead of the program memory")
+        .long("--no-flash-strings")
+        .takes_value(false);
     let arg_emit_llvm = Arg::with_name("emit_llvm")
         .help("Emit LLVM IR")
         .long("--emit-llvm")
@@ -48,8 +89,15 @@ fn parse_arguments<'a>(app: App<'a, '_>) -> ArgMatches<'a> {
         .arg(arg_opt)
         .arg(arg_baudrate)
         .arg(arg_port)
+        .arg(arg_board)
         .arg(arg_cpu)
         .arg(arg_remove_hex)
+        .arg(arg_module_path)
+        .arg(arg_stdlib_path)
+        .arg(arg_source)
+        .arg(arg_include_dir)
+        .arg(arg_project)
+        .arg(arg_no_flash_strings)
         .arg(arg_emit_llvm)
         .get_matches()
 }
@@ -64,15 +112,33 @@ fn main() -> Result<(), Box<dyn Error>> {
     let matches = parse_arguments(app);
     let file = matches.value_of("file").expect("no input files");
     let port = matches.value_of("port");
-    let cpu = matches.value_of("cpu").unwrap();
+    let mut board = get_board(matches.value_of("board").unwrap())?;
+    if let Some(cpu) = matches.value_of("cpu") {
+        board.mcu = cpu.to_string();
+    }
+    let cpu = board.mcu.as_str();
     let optimization_level = matches.value_of("opt_level").unwrap().parse::<u8>()?;
 
-    let compiler_flags = CompilerFlags::new(optimization_level);
+    let module_paths = matches
+        .values_of("module_path")
+        .map(|paths| paths.map(|path| path.to_string()).collect())
+        .unwrap_or_default();
+
+    let stdlib_path = get_stdlib_path(matches.value_of("stdlib_path"));
+
+    let compiler_flags = CompilerFlags::new(
+        optimization_level,
+        module_paths,
+        stdlib_path.clone(),
+        cpu.to_owned(),
+        !matches.is_present("no_flash_strings"),
+        board.pin_module.clone(),
+    );
 
     // Generate assembly from given file
     let ir_path = format!("{}.ll", file);
-    let assembly = match get_assembly(file.to_string(), compiler_flags) {
-        Ok(llvm_string) => llvm_string,
+    let (assembly, imported_modules) = match get_assembly(file.to_string(), compiler_flags) {
+        Ok(result) => result,
         Err(e) => panic!("{}", e),
     };
     write(&ir_path, assembly.to_string())?;
@@ -80,26 +146,71 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Generate object
     let object = static_compiler(&ir_path, cpu, optimization_level);
 
+    // The project file in the directory of the source file is read by default
+    let project_file = match matches.value_of("project") {
+        Some(project_file) => Some(project_file.to_string()),
+        None => Path::new(file)
+            .parent()
+            .map(|dir| dir.join(PROJECT_FILE))
+            .filter(|path| path.is_file())
+            .map(|path| path.to_str().unwrap().to_string()),
+    };
+    let mut project = match project_file {
+        Some(project_file) => Project::read(&project_file)?,
+        None => Project::new(),
+    };
+    if let Some(sources) = matches.values_of("source") {
+        project
+            .sources
+            .extend(sources.map(|source| source.to_string()));
+    }
+    if let Some(include_dirs) = matches.values_of("include_dir") {
+        project
+            .include_dirs
+            .extend(include_dirs.map(|dir| dir.to_string()));
+    }
+
     // Run avr-gcc to create hex file
-    let avr_compiler_flags = AVRCompilerFlags::new(16000000, cpu.to_owned());
+    let avr_compiler_flags = AVRCompilerFlags::new(
+        board.f_cpu,
+        cpu.to_owned(),
+        board.variant.clone(),
+        board.get_defines(),
+        stdlib_path,
+        project.sources,
+        project.include_dirs,
+        imported_modules,
+    );
     let hex = avrgcc(&object, avr_compiler_flags);
 
-    // Check the size of hex
-    let hex_file = File::open(&hex)?;
-    let file_size = hex_file.metadata()?.len();
-    if file_size > 30 * 1024 {
+    // Check the program fits in the memories of the board
+    let (flash, sram) = get_memory_usage(&format!("{}.elf", &object));
+    println!(
+        "The program uses {} of {} bytes of the flash memory and {} of {} bytes of SRAM.",
+        flash, board.flash_size, sram, board.sram_size
+    );
+    if flash > board.flash_size {
         eprintln!(
-            "WARNING: The size of the result file ({}KB) is larger than 30KB.",
-            file_size / 1024
+            "WARNING: The program is too large for the flash memory of {}.",
+            board.name
         );
     }
+    if sram > board.sram_size {
+        eprintln!("WARNING: The globals do not fit in SRAM of {}.", board.name);
+    }
 
     // Run avrdude to flash memory if a serial port is presented
     if let Some(port) = port {
+        let baudrate = match matches.value_of("baudrate") {
+            Some(baudrate) => baudrate.parse::<u64>()?,
+            None => board.upload_speed,
+        };
         let avrdude_flags = AVRDudeFlags::new(
             cpu.to_owned(),
             port,
-            matches.value_of("baudrate").unwrap().parse::<u64>()?,
+            board.upload_protocol.clone(),
+            baudrate,
+            board.use_1200bps_touch,
         );
         avrdude(&hex, avrdude_flags);
     }
@@ -1,6 +1,9 @@
 pub use arduino::avrdude::{avrdude, AVRDudeFlags};
-pub use arduino::avrgcc::{avrgcc, AVRCompilerFlags};
-pub use utils::{get_arduino_dir, static_compiler};
+pub use arduino::avrgcc::{avrgcc, get_memory_usage, AVRCompilerFlags};
+pub use arduino::boards::{get_board, Board, DEFAULT_BOARD};
+pub use project::{Project, PROJECT_FILE};
+pub use utils::{get_arduino_dir, get_stdlib_path, static_compiler};
 
 mod arduino;
+mod project;
 mod utils;
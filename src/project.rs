@@ -0,0 +1,76 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+/// The name of the project file which is read from the directory of the source file
+pub const PROJECT_FILE: &str = "dspython.project";
+
+/// Settings of a project read from a project file.
+///
+/// Each line of the file is `key = value`, and `#` starts a comment:
+/// ```text
+/// source = drivers/motor.c
+/// include_dir = drivers
+/// ```
+/// The paths are relative to the directory of the project file.
+#[derive(Default)]
+pub struct Project {
+    pub sources: Vec<String>,
+    pub include_dirs: Vec<String>,
+}
+
+impl Project {
+    pub fn new() -> Self {
+        Project {
+            sources: vec![],
+            include_dirs: vec![],
+        }
+    }
+
+    /// Read a project file, or return an error with the line which can not be read
+    pub fn read(path: &str) -> Result<Self, String> {
+        let source =
+            read_to_string(path).map_err(|e| format!("can't open file '{}': {}", path, e))?;
+        let project_dir = Path::new(path).parent().unwrap_or(Path::new("."));
+
+        let mut project = Project::new();
+        for (line_number, line) in source.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = match line.find('=') {
+                Some(index) => (line[..index].trim(), line[index + 1..].trim()),
+                None => {
+                    return Err(format!(
+                        "{}:{}: expected 'key = value', but found '{}'",
+                        path,
+                        line_number + 1,
+                        line
+                    ));
+                }
+            };
+            if value.is_empty() {
+                return Err(format!(
+                    "{}:{}: expected a path for '{}'",
+                    path,
+                    line_number + 1,
+                    key
+                ));
+            }
+            let value = project_dir.join(value).to_string_lossy().to_string();
+            match key {
+                "source" => project.sources.push(value),
+                "include_dir" => project.include_dirs.push(value),
+                _ => {
+                    return Err(format!(
+                        "{}:{}: unknown key '{}'",
+                        path,
+                        line_number + 1,
+                        key
+                    ));
+                }
+            }
+        }
+        Ok(project)
+    }
+}
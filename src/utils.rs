@@ -10,6 +10,19 @@ pub fn get_arduino_dir() -> String {
     return arduino_dir;
 }
 
+/// Get the location of the Arduino software, or `None` if `ARDUINO_DIR` is not set
+pub fn find_arduino_dir() -> Option<String> {
+    env::var("ARDUINO_DIR").ok()
+}
+
+/// Get the directory of the standard libraries given by `--stdlib-path`
+/// or the environment variable `DSPYTHON_STDLIB_PATH`.
+/// `None` means the standard libraries embedded in the compiler.
+pub fn get_stdlib_path(arg: Option<&str>) -> Option<String> {
+    arg.map(|path| path.to_string())
+        .or_else(|| env::var("DSPYTHON_STDLIB_PATH").ok())
+}
+
 /// Generate non-linked object file from llvm ir
 pub fn static_compiler(ir_path: &str, cpu: &str, optimization_level: u8) -> String {
     let out = format!("{}.o", ir_path);
@@ -0,0 +1,30 @@
+"""
+Short-circuit `and` and `or`
+"""
+
+from arduino import *
+
+
+def check(name: str, result: bool) -> bool:
+    println(name)
+    return result
+
+
+def setup():
+    serial_begin(9600)
+
+    # Only "a" is printed because `and` stops at the first false operand
+    if check("a", False) and check("b", True):
+        println("unreachable")
+
+    # Only "c" is printed because `or` stops at the first true operand
+    if check("c", True) or check("d", False):
+        println("or")
+
+    value = analog_read(A0)
+    if value < 100 or value > 900:
+        println("out of range")
+
+
+def loop():
+    return
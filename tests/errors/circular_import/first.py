@@ -0,0 +1,7 @@
+"""
+Imports `second`, which imports this module again
+"""
+
+import second
+
+value = 1
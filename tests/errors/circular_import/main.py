@@ -0,0 +1,16 @@
+"""
+Fails to compile: circular import: first -> second -> first
+"""
+
+from arduino import *
+
+import first
+
+
+def setup():
+    serial_begin(9600)
+    println(first.value)
+
+
+def loop():
+    return
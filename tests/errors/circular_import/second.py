@@ -0,0 +1,7 @@
+"""
+Imports `first`, which is still being imported
+"""
+
+import first
+
+value = 2
@@ -0,0 +1,16 @@
+"""
+Fails to compile: the initial value of a persistent variable must be a literal
+"""
+
+from arduino import *
+
+threshold: Persistent[int] = analog_read(A0)
+
+
+def setup():
+    serial_begin(9600)
+    println(threshold)
+
+
+def loop():
+    return
@@ -0,0 +1,15 @@
+"""
+Fails to compile: relative imports are not supported
+"""
+
+from arduino import *
+
+from . import led
+
+
+def setup():
+    led.setup()
+
+
+def loop():
+    return
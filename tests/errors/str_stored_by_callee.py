@@ -0,0 +1,24 @@
+"""
+Fails to compile: `remember` stores its argument to a global,
+so the concatenated str on the stack of `setup` can not be passed to it
+"""
+
+from arduino import *
+
+last = ""
+
+
+def remember(message: str):
+    global last
+    last = message
+
+
+def setup():
+    serial_begin(9600)
+    name = "Arduino"
+    remember("Hello, " + name)
+    remember("Hello")
+
+
+def loop():
+    println(last)
@@ -0,0 +1,23 @@
+"""
+Fails to compile: the concatenated str returned by `identity` is the buffer
+on the stack of `greeting`, so it can not be returned from `greeting`
+"""
+
+from arduino import *
+
+
+def identity(s: str) -> str:
+    return s
+
+
+def greeting(name: str) -> str:
+    return identity("Hello, " + name)
+
+
+def setup():
+    serial_begin(9600)
+    println(greeting("Arduino"))
+
+
+def loop():
+    return
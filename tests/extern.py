@@ -0,0 +1,26 @@
+"""
+Calling C functions of the Arduino core
+"""
+
+from arduino import *
+from arduino.ffi import extern
+
+
+@extern("millis")
+def millis() -> uint32:
+    ...
+
+
+@extern("analogReference")
+def analog_reference(_mode: uint8) -> None:
+    ...
+
+
+def setup():
+    serial_begin(9600)
+    analog_reference(1)
+
+
+def loop():
+    println(millis())
+    delay(1000)
@@ -0,0 +1,24 @@
+"""
+f-strings passed to print and println
+"""
+
+from arduino import *
+
+
+def setup():
+    serial_begin(9600)
+
+    name = "Arduino"
+    x = 42
+    ratio = 0.125
+
+    println(f"Hello, {name}!")
+    println(f"x = {x}, x + 1 = {x + 1}")
+    println(f"dec = {x:d}, hex = {x:x}, HEX = {x:X}, oct = {x:o}, bin = {x:b}")
+    println(f"ratio = {ratio:.2f}, x as float = {x:.1f}")
+    print(f"{x}")
+    println(f" and {ratio}")
+
+
+def loop():
+    return
@@ -0,0 +1,18 @@
+"""
+A user module imported by `main.py`
+"""
+
+from arduino import *
+
+pin = 13
+
+
+def setup():
+    pin_mode(pin, OUTPUT)
+
+
+def blink(duration: int):
+    digital_write(pin, HIGH)
+    delay(duration)
+    digital_write(pin, LOW)
+    delay(duration)
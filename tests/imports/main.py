@@ -0,0 +1,20 @@
+"""
+User module imports
+"""
+
+from arduino import *
+
+import led
+from led import blink as blink_led
+
+
+def setup():
+    # Does not collide with `led.setup`
+    led.setup()
+    serial_begin(9600)
+    println("LED pin:", led.pin)
+
+
+def loop():
+    blink_led(500)
+    led.blink(1000)
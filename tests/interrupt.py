@@ -0,0 +1,32 @@
+"""
+Count the presses of a button on pin 2 with an external interrupt
+"""
+
+from arduino import *
+from arduino.ffi import interrupt
+
+BUTTON_PIN = 2
+
+presses: uint16 = 0
+
+
+@interrupt
+def on_press():
+    global presses
+    presses = presses + 1
+
+
+def setup():
+    serial_begin(9600)
+    pin_mode(BUTTON_PIN, INPUT_PULLUP)
+    attach_interrupt(BUTTON_PIN, on_press, FALLING)
+
+
+def loop():
+    # A 16-bit global cannot be read atomically on AVR
+    no_interrupts()
+    count = presses
+    interrupts()
+
+    println(count)
+    delay(500)
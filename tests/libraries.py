@@ -0,0 +1,36 @@
+"""
+Bindings of the Arduino libraries
+"""
+
+import arduino.spi as spi
+from arduino import *
+from arduino import eeprom, servo, wire
+
+
+def setup():
+    serial_begin(9600)
+
+    servo.attach(0, 9)
+    wire.begin()
+    spi.begin()
+
+    eeprom.update(0, 42)
+    println("EEPROM[0] =", eeprom.read(0), "of", eeprom.length())
+
+
+def loop():
+    servo.write(0, (servo.read(0) + 10) % 180)
+
+    # Read a byte from the I2C device at 0x68
+    wire.begin_transmission(0x68)
+    wire.write(0x75)
+    wire.end_transmission()
+    wire.request_from(0x68, 1)
+    if wire.available():
+        println(wire.read())
+
+    spi.begin_transaction(1000000, MSBFIRST, spi.SPI_MODE0)
+    spi.transfer(0xFF)
+    spi.end_transaction()
+
+    delay(100)
@@ -0,0 +1,23 @@
+"""
+The math functions of avr-libc with the Python semantics
+"""
+
+from arduino import *
+
+
+def setup():
+    serial_begin(9600)
+
+    println(sqrt(2))
+    println(pow(2, 10), pow(2.0, 0.5))
+    println(floor(-2.5), ceil(2.1))
+    # Banker's rounding
+    println(round(0.5), round(1.5), round(2.5))
+    println(atan2(1.0, 1.0), exp(1), log(EULER))
+    println(sin(radians(30)), acos(0.5), tanh(1.0))
+    println(hypot(3, 4), cbrt(27), log10(1000))
+    println(fabs(-1.5), fmod(-7.0, 3.0), trunc(-2.5))
+
+
+def loop():
+    pass
@@ -0,0 +1,21 @@
+"""
+Reassigning unsigned 32-bit locals
+"""
+
+from arduino import *
+
+
+def setup():
+    serial_begin(9600)
+
+    # `started` stays uint32 when an int is assigned to it
+    started = millis()
+    println(started)
+    started = 0
+    println(started)
+    started = micros()
+    println(started)
+
+
+def loop():
+    return
@@ -0,0 +1,29 @@
+"""
+Only the imported names of the standard libraries are visible
+"""
+
+import arduino
+from arduino import HIGH, LOW, OUTPUT, pin_mode
+from arduino import digital_write as write
+
+
+# Does not collide with the builtin `min`
+def min(a: int, b: int, c: int) -> int:
+    if b < a:
+        a = b
+    if c < a:
+        a = c
+    return a
+
+
+def setup():
+    pin_mode(arduino.LED_BUILTIN, OUTPUT)
+    println(min(3, 1, 2))
+    println(max(1, 2))
+
+
+def loop():
+    write(arduino.LED_BUILTIN, HIGH)
+    arduino.delay(1000)
+    write(arduino.LED_BUILTIN, LOW)
+    arduino.delay(1000)
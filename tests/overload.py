@@ -0,0 +1,46 @@
+"""
+Overload resolution
+"""
+
+from typing import overload
+
+from arduino import *
+
+
+@overload
+def area(side: int) -> int:
+    return side * side
+
+
+@overload
+def area(radius: float) -> float:
+    return PI * radius * radius
+
+
+# Overloads can also be defined by mangled names
+def scale__i_f__(x: int, factor: float) -> float:
+    return x * factor
+
+
+def setup():
+    serial_begin(9600)
+
+    println(area(3))
+    println(area(1.5))
+    println(scale(2, 0.5))
+
+    # `min(int, int)` does not accept a float, so `min(float, float)` is chosen
+    println(min(1, 2.5))
+
+    # uint32 is neither narrowed to int nor converted to float,
+    # and the literal is converted to uint32 because it can hold 100
+    println(min(millis(), 100))
+    println(max(millis(), 100))
+    println(abs(millis()))
+    elapsed: int32 = -5
+    println(abs(elapsed))
+    println(max(elapsed, 100))
+
+
+def loop():
+    return
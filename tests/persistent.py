@@ -0,0 +1,28 @@
+"""
+Calibrate the threshold of a light sensor with a button.
+The threshold is kept in the EEPROM after reset.
+"""
+
+from arduino import *
+
+threshold: Persistent[int] = 512
+boots: Persistent[uint32] = 0
+
+
+def setup():
+    global boots
+    serial_begin(9600)
+    pin_mode(2, INPUT_PULLUP)
+    pin_mode(LED_BUILTIN, OUTPUT)
+    boots = boots + 1
+    println(f"Boot {boots}, threshold {threshold}")
+
+
+def loop():
+    global threshold
+    light = analog_read(A0)
+    if digital_read(2) == LOW:
+        threshold = light
+        println("Calibrated:", threshold)
+        delay(500)
+    digital_write(LED_BUILTIN, light > threshold)
@@ -0,0 +1,30 @@
+"""
+Printing values of various types
+"""
+
+from arduino import *
+
+
+def setup():
+    serial_begin(9600)
+
+    x = 255
+    ratio = 0.5
+
+    println(True, False)
+
+    # Unsigned and 32-bit integers are printed by their own wrappers
+    small: uint8 = 200
+    count: uint16 = 65535
+    offset: int32 = -100000
+    println(small, count, offset, millis())
+    println(3.25, -0.5)
+    println("x =", x, "ratio =", ratio, sep=" ")
+    println(f"x = {x}, hex = {x:x}, HEX = {x:X}, bin = {x:b}")
+    println(f"ratio = {ratio:.3f}")
+    print("no newline", end="")
+    println()
+
+
+def loop():
+    return
@@ -0,0 +1,29 @@
+"""
+print and println with several arguments and the sep and end keywords
+"""
+
+from arduino import *
+
+
+def setup():
+    serial_begin(9600)
+
+    x = 1
+    y = 2.5
+
+    # Arguments are separated by a space, and println ends with "\r\n"
+    println("x:", x, "y:", y)
+    println()
+
+    # print ends with "\n"
+    print("a", "b", "c")
+
+    println(x, y, sep=", ")
+    println(x, y, sep="")
+    print(x, y, sep=" | ", end=";\n")
+    print("no newline", end="")
+    println(end="\n")
+
+
+def loop():
+    return
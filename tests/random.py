@@ -0,0 +1,22 @@
+"""
+Dim an LED randomly within the range set by a potentiometer
+"""
+
+from arduino import *
+
+LED_PIN = 9
+
+
+def setup():
+    serial_begin(9600)
+    # The noise of an unconnected pin
+    random_seed(analog_read(A5))
+
+
+def loop():
+    limit = map(analog_read(A0), 0, 1023, 0, 255)
+    brightness = constrain(random(-20, 300), 0, limit)
+    analog_write(LED_PIN, brightness)
+
+    println(random(1.0))
+    delay(100)
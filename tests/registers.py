@@ -0,0 +1,21 @@
+"""
+Toggle the built-in LED (PB5) of Arduino Uno through the registers
+"""
+
+from arduino import delay
+from arduino.registers import DDRB, PINB, PORTB
+
+LED_MASK = 0x20
+
+
+def setup():
+    global DDRB, PORTB
+    DDRB = DDRB | LED_MASK
+    PORTB = PORTB & ~LED_MASK
+
+
+def loop():
+    global PINB
+    # Writing 1 to a bit of PINx toggles the bit of PORTx
+    PINB = LED_MASK
+    delay(500)
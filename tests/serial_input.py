@@ -0,0 +1,66 @@
+"""
+Control the built-in LED with the commands from the host
+
+on / off      Turn the LED on or off
+blink <n>     Blink the LED n times
+speed         Ask for the interval of blinking in milliseconds
+"""
+
+from arduino import *
+
+interval: int32 = 200
+
+
+def setup():
+    serial_begin(9600)
+    serial_set_timeout(5000)
+    pin_mode(LED_BUILTIN, OUTPUT)
+
+
+# Get n of "blink <n>", or -1 if the line is another command
+def parse_blink(command: str) -> int:
+    prefix = "blink "
+    if len(command) <= len(prefix):
+        return -1
+    i = 0
+    while i < len(prefix):
+        if command[i] != prefix[i]:
+            return -1
+        i = i + 1
+
+    count = 0
+    while i < len(command):
+        # The character codes of the digits are 48 to 57
+        digit = command[i] - 48
+        if digit < 0 or digit > 9:
+            return -1
+        count = count * 10 + digit
+        i = i + 1
+    return count
+
+
+def blink(count: int):
+    while count > 0:
+        digital_write(LED_BUILTIN, HIGH)
+        delay(interval)
+        digital_write(LED_BUILTIN, LOW)
+        delay(interval)
+        count = count - 1
+
+
+def loop():
+    global interval
+    if serial_available() > 0:
+        command = serial_read_line(16)
+        count = parse_blink(command)
+        if command == "on":
+            digital_write(LED_BUILTIN, HIGH)
+        elif command == "off":
+            digital_write(LED_BUILTIN, LOW)
+        elif command == "speed":
+            println("Interval in milliseconds?")
+            interval = serial_parse_int()
+        elif count >= 0:
+            blink(count)
+        else:
+            println("Unknown command:", command)
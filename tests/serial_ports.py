@@ -0,0 +1,28 @@
+"""
+Forward the data between the USB serial port and a software serial port
+"""
+
+from arduino import *
+from arduino.serial import Serial, SerialPort
+from arduino.software_serial import SoftwareSerial
+
+bridge: SerialPort = 0
+
+
+def setup():
+    global bridge
+    Serial.begin(9600)
+    bridge = SoftwareSerial(10, 11)
+    bridge.begin(4800)
+    port = bridge
+    port.println("Hello")
+
+
+def forward(source: SerialPort, destination: SerialPort):
+    if source.available() > 0:
+        destination.write(source.read())
+
+
+def loop():
+    forward(bridge, Serial)
+    forward(Serial, bridge)
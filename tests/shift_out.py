@@ -0,0 +1,37 @@
+"""
+Count up on the LEDs of a 74HC595 shift register and beep on overflow
+"""
+
+from arduino import *
+
+LATCH_PIN = 8
+CLOCK_PIN = 12
+DATA_PIN = 11
+BUZZER_PIN = 9
+ECHO_PIN = 7
+
+counter: uint8 = 0
+
+
+def setup():
+    serial_begin(9600)
+    pin_mode(LATCH_PIN, OUTPUT)
+    pin_mode(CLOCK_PIN, OUTPUT)
+    pin_mode(DATA_PIN, OUTPUT)
+    pin_mode(ECHO_PIN, INPUT)
+
+
+def loop():
+    global counter
+
+    digital_write(LATCH_PIN, LOW)
+    shift_out(DATA_PIN, CLOCK_PIN, MSBFIRST, counter)
+    digital_write(LATCH_PIN, HIGH)
+
+    counter = counter + 1
+    if counter == 0:
+        tone(BUZZER_PIN, 440, 100)
+
+    # Wait at most 30 ms for a pulse
+    println(pulse_in(ECHO_PIN, HIGH, 30000))
+    delay(200)
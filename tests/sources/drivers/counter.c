@@ -0,0 +1,6 @@
+#include "counter.h"
+
+static uint16_t count = 0;
+
+void counter_increase(void) { count++; }
+uint16_t counter_get(void) { return count; }
@@ -0,0 +1,4 @@
+#include <stdint.h>
+
+void counter_increase(void);
+uint16_t counter_get(void);
@@ -0,0 +1,26 @@
+"""
+C sources listed in `dspython.project` are linked with the program
+"""
+
+from arduino import *
+from arduino.ffi import extern
+
+
+@extern("counter_increase")
+def counter_increase() -> None:
+    ...
+
+
+@extern("counter_get")
+def counter_get() -> uint16:
+    ...
+
+
+def setup():
+    serial_begin(9600)
+
+
+def loop():
+    counter_increase()
+    println(counter_get())
+    delay(1000)
@@ -0,0 +1,33 @@
+"""
+String operations
+"""
+
+from arduino import *
+
+
+def greet(message: str):
+    println(message)
+
+
+def setup():
+    serial_begin(9600)
+
+    # The concatenated str is only valid until setup returns
+    name = "Arduino"
+    message = "Hello, " + name
+    greet(message)
+    println(len(message))
+
+    if message == "Hello, Arduino":
+        println("equal")
+    if message != "Hello":
+        println("not equal")
+
+    # Character code of the first and the last letters
+    println(message[0])
+    last: int32 = -1
+    println(message[last])
+
+
+def loop():
+    return
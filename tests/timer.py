@@ -0,0 +1,32 @@
+"""
+Sample an analog input at 100 Hz with a Timer1 interrupt
+"""
+
+from arduino import *
+from arduino import timer
+from arduino.ffi import isr
+
+samples: uint32 = 0
+last_value: int = 0
+
+
+@isr("TIMER1_COMPA_vect")
+def sample():
+    global samples, last_value
+    last_value = analog_read(A0)
+    samples = samples + 1
+
+
+def setup():
+    serial_begin(9600)
+    timer.set_frequency(100)
+
+
+def loop():
+    no_interrupts()
+    count = samples
+    value = last_value
+    interrupts()
+
+    print(count, value, sep=": ")
+    delay(1000)